@@ -0,0 +1,61 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+pub mod utils;
+use utils::*;
+
+criterion_group!(batch_verify, benchmark_batch_verification);
+criterion_main!(batch_verify);
+
+/// Amounts of proofs verified at once, all of them hashing a single block.
+fn batch_sizes() -> Vec<usize> {
+    vec![1, 4, 16, 64]
+}
+
+pub fn benchmark_batch_verification(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch_verify");
+    configure_group(&mut group);
+
+    let ci = random_input_for_desired_blocks(1);
+    let circuit = BenchCircuitRunner::create_circuit_for_packed_inputs(ci).unwrap();
+    let params = BenchCircuitRunner::create_params(circuit.k());
+    let vk = BenchCircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = BenchCircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+
+    let max_batch_size = batch_sizes().into_iter().max().unwrap();
+    let proven: Vec<(Vec<BenchField>, Vec<u8>)> = (0..max_batch_size)
+        .map(|_| {
+            let ci = random_input_for_desired_blocks(1);
+            let expected_output_fields = ci.4.to_vec();
+            let circuit = BenchCircuitRunner::create_circuit_for_packed_inputs(ci).unwrap();
+            let proof =
+                BenchCircuitRunner::create_proof(&expected_output_fields, circuit, &params, &pk)
+                    .unwrap()
+                    .proof;
+            (expected_output_fields, proof)
+        })
+        .collect();
+    // A batch of invalid proofs would only time the rejection path.
+    let (public_inputs, proof) = &proven[0];
+    BenchCircuitRunner::verify_with_vk(&vk, &params, public_inputs, proof)
+        .expect("The benched proofs must verify");
+
+    for batch_size in batch_sizes() {
+        let batch: Vec<(&[BenchField], &[u8])> = proven[..batch_size]
+            .iter()
+            .map(|(public_inputs, proof)| (public_inputs.as_slice(), proof.as_slice()))
+            .collect();
+        group.throughput(Throughput::Elements(batch_size as u64));
+
+        group.bench_function(BenchmarkId::new("sequential", batch_size), |b| {
+            b.iter(|| {
+                for (public_inputs, proof) in &batch {
+                    BenchCircuitRunner::verify_with_vk(&vk, &params, public_inputs, proof).unwrap()
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("batched", batch_size), |b| {
+            b.iter(|| BenchCircuitRunner::verify_batch(&vk, &params, &batch).unwrap())
+        });
+    }
+    group.finish()
+}
@@ -2,7 +2,6 @@ use criterion::{
     criterion_group, criterion_main, BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput,
 };
 use criterion::measurement::WallTime;
-use blake2b_halo2::usage_utils::circuit_runner::CircuitRunner;
 
 pub mod utils;
 use utils::*;
@@ -17,7 +16,11 @@ pub fn benchmark_mocked_proving(c: &mut Criterion) {
     for amount_of_blocks in benchmarking_block_sizes() {
         group.throughput(Throughput::Bytes(amount_of_blocks as u64));
 
-        benchmark_optimization_with_amount_of_blocks(&mut group, amount_of_blocks, "opt_recycle");
+        benchmark_optimization_with_amount_of_blocks(
+            &mut group,
+            amount_of_blocks,
+            &format!("opt_recycle_{}", witness_generation_mode()),
+        );
     }
     group.finish()
 }
@@ -31,11 +34,12 @@ fn benchmark_optimization_with_amount_of_blocks(
         b.iter_batched(
             || {
                 let ci = random_input_for_desired_blocks(amount_of_blocks);
-                let circuit = CircuitRunner::create_circuit_for_packed_inputs(ci.clone());
+                let circuit =
+                    BenchCircuitRunner::create_circuit_for_packed_inputs(ci.clone()).unwrap();
                 (circuit, ci.4)
             },
             |(circuit, expected)| {
-                CircuitRunner::mock_prove_with_public_inputs_ref(&expected, &circuit)
+                BenchCircuitRunner::mock_prove_with_public_inputs_ref(&expected, &circuit).unwrap()
             },
             BatchSize::SmallInput,
         )
@@ -0,0 +1,118 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+pub mod utils;
+use utils::*;
+
+criterion_group!(multi_circuit, benchmark_multi_circuit_proving);
+criterion_main!(multi_circuit);
+
+/// Amounts of circuits proven at once, all of them hashing a single block.
+fn circuit_counts() -> Vec<usize> {
+    vec![1, 2, 4, 8, 16]
+}
+
+/// Compares proving and verifying every circuit in its own proof against proving all of them in a
+/// single proof. The single proof amortizes the commitment and opening costs, but its evaluations
+/// grow with the amount of circuits.
+pub fn benchmark_multi_circuit_proving(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi_circuit");
+    configure_group(&mut group);
+
+    let ci = random_input_for_desired_blocks(1);
+    let circuit = BenchCircuitRunner::create_circuit_for_packed_inputs(ci).unwrap();
+    let k = circuit.k();
+    let params = BenchCircuitRunner::create_params(k);
+    let vk = BenchCircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = BenchCircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+
+    for circuit_count in circuit_counts() {
+        let (digests, circuits): (Vec<Vec<BenchField>>, Vec<_>) = (0..circuit_count)
+            .map(|_| {
+                let ci = random_input_for_desired_blocks(1);
+                let digest = ci.4.to_vec();
+                (digest, BenchCircuitRunner::create_circuit_for_packed_inputs(ci).unwrap())
+            })
+            .unzip();
+        let instances: Vec<[&[BenchField]; 1]> =
+            digests.iter().map(|digest| [digest.as_slice()]).collect();
+        let instances: Vec<&[&[BenchField]]> =
+            instances.iter().map(|columns| columns.as_slice()).collect();
+        let no_commitments = vec![&[][..]; circuit_count];
+        group.throughput(Throughput::Elements(circuit_count as u64));
+
+        let separate_proofs: Vec<Vec<u8>> = digests
+            .iter()
+            .zip(&circuits)
+            .map(|(digest, circuit)| {
+                BenchCircuitRunner::create_proof(digest, circuit.clone(), &params, &pk)
+                    .unwrap()
+                    .proof
+            })
+            .collect();
+        let artifacts = BenchCircuitRunner::create_proof_for_circuits(
+            &instances,
+            circuits.clone(),
+            &params,
+            &pk,
+        )
+        .unwrap();
+        println!(
+            "multi_circuit/{circuit_count}: k = {k}, separate proofs = {} bytes, single proof = {} bytes",
+            separate_proofs.iter().map(Vec::len).sum::<usize>(),
+            artifacts.stats.proof_bytes
+        );
+
+        // Circuits that are not satisfied would only time the rejection path.
+        BenchCircuitRunner::verify_with_vk(&vk, &params, &digests[0], &separate_proofs[0])
+            .expect("The separate proofs must verify");
+        BenchCircuitRunner::verify_for_circuits(
+            &vk,
+            &params,
+            &no_commitments,
+            &instances,
+            &artifacts.proof,
+        )
+        .expect("The single proof must verify");
+
+        group.bench_function(BenchmarkId::new("prove_separately", circuit_count), |b| {
+            b.iter(|| {
+                for (digest, circuit) in digests.iter().zip(&circuits) {
+                    BenchCircuitRunner::create_proof(digest, circuit.clone(), &params, &pk)
+                        .unwrap();
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("prove_together", circuit_count), |b| {
+            b.iter(|| {
+                BenchCircuitRunner::create_proof_for_circuits(
+                    &instances,
+                    circuits.clone(),
+                    &params,
+                    &pk,
+                )
+                .unwrap()
+            })
+        });
+
+        group.bench_function(BenchmarkId::new("verify_separately", circuit_count), |b| {
+            b.iter(|| {
+                for (digest, proof) in digests.iter().zip(&separate_proofs) {
+                    BenchCircuitRunner::verify_with_vk(&vk, &params, digest, proof).unwrap();
+                }
+            })
+        });
+        group.bench_function(BenchmarkId::new("verify_together", circuit_count), |b| {
+            b.iter(|| {
+                BenchCircuitRunner::verify_for_circuits(
+                    &vk,
+                    &params,
+                    &no_commitments,
+                    &instances,
+                    &artifacts.proof,
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish()
+}
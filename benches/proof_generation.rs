@@ -1,8 +1,5 @@
-use blake2b_halo2::usage_utils::circuit_runner::CircuitRunner;
 use criterion::measurement::WallTime;
 use criterion::{criterion_group, criterion_main, BenchmarkGroup, BenchmarkId, Criterion, Throughput};
-use midnight_curves::bls12_381::Bls12;
-use midnight_proofs::poly::kzg::params::ParamsKZG;
 
 pub mod utils;
 use utils::*;
@@ -13,33 +10,37 @@ criterion_main!(proof);
 pub fn benchmark_proof_generation(c: &mut Criterion) {
     let mut group = c.benchmark_group("proof");
     configure_group(&mut group);
-
-    let params = ParamsKZG::<Bls12>::unsafe_setup(17, &mut rand::thread_rng());
+    let mut params_cache = BenchParamsCache::new();
 
     for amount_of_blocks in benchmarking_block_sizes() {
         group.throughput(Throughput::Bytes(amount_of_blocks as u64));
 
-        benchmark_proof(&params, &mut group, amount_of_blocks, "opt_recycle");
+        benchmark_proof(&mut group, &mut params_cache, amount_of_blocks, "opt_recycle");
     }
     group.finish()
 }
 
 fn benchmark_proof(
-    params: &ParamsKZG<Bls12>,
     group: &mut BenchmarkGroup<WallTime>,
+    params_cache: &mut BenchParamsCache,
     amount_of_blocks: usize,
     name: &str,
 ) {
     let ci = random_input_for_desired_blocks(amount_of_blocks);
     let expected_output_fields = ci.4;
 
-    let circuit = CircuitRunner::create_circuit_for_packed_inputs(ci);
-    let vk = CircuitRunner::create_vk(&circuit, params);
-    let pk = CircuitRunner::create_pk(&circuit, vk.clone());
+    let circuit = BenchCircuitRunner::create_circuit_for_packed_inputs(ci).unwrap();
+    let params = params_cache.params_for(&circuit);
+    let vk = BenchCircuitRunner::create_vk(&circuit, params).unwrap();
+    let pk = BenchCircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+    let artifacts =
+        BenchCircuitRunner::create_proof(&expected_output_fields, circuit.clone(), params, &pk)
+            .unwrap();
+    report_proof_size(name, amount_of_blocks, circuit.k(), &artifacts);
 
     group.bench_function(BenchmarkId::new(name, amount_of_blocks), |b| {
         b.iter(|| {
-            CircuitRunner::create_proof(&expected_output_fields, circuit.clone(), params, &pk)
+            BenchCircuitRunner::create_proof(&expected_output_fields, circuit.clone(), params, &pk)
         })
     });
 }
@@ -0,0 +1,44 @@
+//! Compares the bytes a verifier receives, the proof and its public inputs, across input sizes and
+//! across the layouts of the digest in its instance column. It prints a table instead of timings.
+
+use blake2b_halo2::usage_utils::blake2b_circuit::DigestLayout;
+use ff::PrimeField;
+
+pub mod utils;
+use utils::*;
+
+fn main() {
+    let mut params_cache = BenchParamsCache::new();
+    println!(
+        "{:>6} | {:<11} | {:>2} | {:>11} | {:>13} | {:>12} | {:>11}",
+        "blocks", "layout", "k", "proof bytes", "public inputs", "public bytes", "total bytes"
+    );
+    for amount_of_blocks in benchmarking_block_sizes() {
+        for digest_layout in [DigestLayout::Bytes, DigestLayout::PackedWords] {
+            let ci = random_input_for_desired_blocks::<BenchField>(amount_of_blocks);
+            let digest: Vec<u8> = ci.4.iter().map(|byte| byte.to_repr().as_ref()[0]).collect();
+            let instance: Vec<BenchField> = digest_layout.instances_for(&digest);
+
+            let circuit = BenchCircuitRunner::create_circuit_for_packed_inputs(ci)
+                .unwrap()
+                .with_digest_layout(digest_layout);
+            let k = circuit.k();
+            let params = params_cache.params_for(&circuit);
+            let vk = BenchCircuitRunner::create_vk(&circuit, params).unwrap();
+            let pk = BenchCircuitRunner::create_pk(&circuit, vk).unwrap();
+            let stats =
+                BenchCircuitRunner::create_proof(&instance, circuit, params, &pk).unwrap().stats;
+
+            println!(
+                "{:>6} | {:<11} | {:>2} | {:>11} | {:>13} | {:>12} | {:>11}",
+                amount_of_blocks,
+                format!("{digest_layout:?}"),
+                k,
+                stats.proof_bytes,
+                stats.public_input_elements,
+                stats.public_input_bytes,
+                stats.total_bytes()
+            );
+        }
+    }
+}
@@ -1,48 +1,85 @@
 use criterion::{BenchmarkGroup, SamplingMode};
 use criterion::measurement::WallTime;
+use ff::PrimeField;
 use midnight_proofs::circuit::Value;
-use midnight_curves::bls12_381::Fq;
+use midnight_curves::bls12_381::{Bls12, Fq};
+use midnight_proofs::poly::kzg::KZGCommitmentScheme;
 use rand::Rng;
-use blake2b_halo2::usage_utils::circuit_runner::Blake2bCircuitInputs;
+use blake2b_halo2::usage_utils::circuit_runner::{
+    Blake2bCircuitInputs, CircuitRunner, ParamsCache, ProofArtifacts,
+};
 use blake2_rfc::blake2b::blake2b;
 
+/// Field of the benchmarked circuits
+pub type BenchField = Fq;
+
+/// Commitment scheme of the benchmarked proofs. Benchmarking another curve supported by
+/// midnight-proofs only needs this and [BenchField] to change.
+pub type BenchCommitmentScheme = KZGCommitmentScheme<Bls12>;
+
+pub type BenchCircuitRunner = CircuitRunner<BenchField, BenchCommitmentScheme>;
+
+/// Parameters of the benchmarked proofs. Every input size is proven with the parameters of the
+/// smallest k its circuit fits in, which are shared by the sizes with the same k.
+pub type BenchParamsCache = ParamsCache<BenchField, BenchCommitmentScheme>;
+
 pub fn benchmarking_block_sizes() -> Vec<usize> {
     vec![1, 5, 10, 20, 30]
 }
 
+/// Name of the witness generation mode the crate was compiled with. Running a bench with and
+/// without `--features parallel` puts both modes side by side in the criterion report.
+pub fn witness_generation_mode() -> &'static str {
+    if cfg!(feature = "parallel") {
+        "parallel"
+    } else {
+        "sequential"
+    }
+}
+
 pub fn sample_size() -> usize {
     30
 }
 
+/// Prints the size of the proofs of a benchmark, which criterion only reports timings for.
+pub fn report_proof_size(name: &str, amount_of_blocks: usize, k: u32, artifacts: &ProofArtifacts) {
+    println!(
+        "{name}/{amount_of_blocks}: k = {k}, proof size = {} bytes, public inputs = {} bytes",
+        artifacts.stats.proof_bytes, artifacts.stats.public_input_bytes
+    );
+}
+
 pub fn configure_group(group: &mut BenchmarkGroup<WallTime>) {
     group.sampling_mode(SamplingMode::Flat);
     group.sample_size(sample_size());
     //group.measurement_time(Duration::from_secs(1000));
 }
 
-pub fn random_input_for_desired_blocks(amount_of_blocks: usize) -> Blake2bCircuitInputs {
+pub fn random_input_for_desired_blocks<F: PrimeField>(
+    amount_of_blocks: usize,
+) -> Blake2bCircuitInputs<F> {
     let mut rng = rand::thread_rng();
 
     let input_size = amount_of_blocks * 128;
     const OUTPUT_SIZE: usize = 64;
     let random_input_bytes: Vec<u8> = (0..input_size).map(|_| rng.gen_range(0..=255)).collect();
-    let random_inputs: &str = &hex::encode(&random_input_bytes);
-    let key: &str = "";
+    let key: &[u8] = &[];
     let output_size = OUTPUT_SIZE;
 
-    let hash_result = run_blake2b(random_inputs, key, output_size);
+    let hash_result = run_blake2b(&random_input_bytes, key, output_size);
 
-    let expected_output_: Vec<Fq> = hash_result.iter().map(|byte| Fq::from(*byte as u64)).collect();
-    let expected_output: [Fq; OUTPUT_SIZE] = expected_output_.try_into().unwrap();
-    let input_values: Vec<Value<Fq>> =
-        random_input_bytes.iter().map(|x| Value::known(Fq::from(*x as u64))).collect();
+    let expected_output_: Vec<F> = hash_result.iter().map(|byte| F::from(*byte as u64)).collect();
+    let expected_output: [F; OUTPUT_SIZE] = expected_output_.try_into().unwrap();
+    let input_values: Vec<Value<F>> =
+        random_input_bytes.iter().map(|x| Value::known(F::from(*x as u64))).collect();
     let key_size = 0;
-    let key_values: Vec<Value<Fq>> = vec![];
+    let key_values: Vec<Value<F>> = vec![];
 
     (input_values, input_size, key_values, key_size, expected_output, OUTPUT_SIZE)
 }
 
-fn run_blake2b(input: &str, key: &str, output_size: usize) -> Vec<u8> {
-    let res = blake2b(output_size, key.as_bytes(), input.as_bytes());
+/// The digest of the raw 'input' bytes, which are the bytes the circuit hashes.
+fn run_blake2b(input: &[u8], key: &[u8], output_size: usize) -> Vec<u8> {
+    let res = blake2b(output_size, key, input);
     res.as_bytes().into()
 }
@@ -1,10 +1,8 @@
-use blake2b_halo2::usage_utils::circuit_runner::CircuitRunner;
+use blake2b_halo2::usage_utils::blake2b_circuit::Blake2bCircuit;
 use criterion::measurement::WallTime;
 use criterion::{
     criterion_group, criterion_main, BatchSize, BenchmarkGroup, BenchmarkId, Criterion, Throughput,
 };
-use midnight_curves::bls12_381::Bls12;
-use midnight_proofs::poly::kzg::params::ParamsKZG;
 
 pub mod utils;
 use utils::*;
@@ -15,30 +13,32 @@ criterion_main!(vk);
 pub fn benchmark_verification_key_generation(c: &mut Criterion) {
     let mut group = c.benchmark_group("verification_key");
     configure_group(&mut group);
-
-    let params = ParamsKZG::<Bls12>::unsafe_setup(17, &mut rand::thread_rng());
+    let mut params_cache = BenchParamsCache::new();
 
     for amount_of_blocks in benchmarking_block_sizes() {
         group.throughput(Throughput::Bytes(amount_of_blocks as u64));
 
-        benchmark_verification_key(&params, &mut group, amount_of_blocks, "opt_recycle");
+        benchmark_verification_key(&mut group, &mut params_cache, amount_of_blocks, "opt_recycle");
     }
     group.finish()
 }
 
 fn benchmark_verification_key(
-    params: &ParamsKZG<Bls12>,
     group: &mut BenchmarkGroup<WallTime>,
+    params_cache: &mut BenchParamsCache,
     amount_of_blocks: usize,
     name: &str,
 ) {
+    let k = Blake2bCircuit::<BenchField>::k_for(amount_of_blocks * 128, 0);
+    let params = params_cache.params_for_k(k);
+
     group.bench_function(BenchmarkId::new(name, amount_of_blocks), |b| {
         b.iter_batched(
             || {
                 let ci = random_input_for_desired_blocks(amount_of_blocks);
-                CircuitRunner::create_circuit_for_packed_inputs(ci.clone())
+                BenchCircuitRunner::create_circuit_for_packed_inputs(ci.clone()).unwrap()
             },
-            |circuit| CircuitRunner::create_vk(&circuit, params),
+            |circuit| BenchCircuitRunner::create_vk(&circuit, params),
             BatchSize::SmallInput,
         )
     });
@@ -59,7 +59,8 @@ impl AdditionMod64Config {
     }
 
     /// This method receives two cells, copies the values of the cells to the trace and then
-    /// calculates the result and carry of the addition and write it in a third row.
+    /// writes the result of the addition in a third row. Both the result value and the carry are
+    /// computed natively by the caller.
     ///
     /// When one of the addition parameters (previous_cell)
     /// is the last cell that was generated in the circuit, by setting the [use_last_cell_as_first_operand]
@@ -72,12 +73,12 @@ impl AdditionMod64Config {
         offset: &mut usize,
         previous_cell: &AssignedBlake2bWord<F>,
         cell_to_copy: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
+        carry_value: Value<bool>,
         use_last_cell_as_first_operand: bool,
         full_number_u64_column: Column<Advice>,
         limbs: [Column<Advice>; 8],
     ) -> Result<(AssignedRow<F>, AssignedBit<F>), Error> {
-        let (result_value, carry_value) =
-            Self::calculate_result_and_carry(previous_cell.value(), cell_to_copy.value());
         let offset_to_enable = *offset - if use_last_cell_as_first_operand { 1 } else { 0 };
         self.q_add.enable(region, offset_to_enable)?;
 
@@ -116,26 +117,4 @@ impl AdditionMod64Config {
 
         Ok((result_row, carry_cell))
     }
-
-    /// Given 2 operand values, known at proof generation time, returns the values holding the
-    /// result of that sum mod 2^64 and the carry value, which must be 0 or 1. Both ranges will be
-    /// constrained by this gate.
-    fn calculate_result_and_carry<F: PrimeField>(
-        lhs: Value<Blake2bWord>,
-        rhs: Value<Blake2bWord>,
-    ) -> (Value<Blake2bWord>, Value<F>) {
-        let result_value = lhs.and_then(|l| rhs.and_then(|r| Value::known(Self::sum_mod_64(l, r))));
-        let carry_value =
-            lhs.and_then(|l| rhs.and_then(|r| Value::known(Self::carry_mod_64(l, r))));
-        (result_value, carry_value)
-    }
-
-    fn sum_mod_64(a: Blake2bWord, b: Blake2bWord) -> Blake2bWord {
-        (((a.0 as u128 + b.0 as u128) % (1u128 << 64)) as u64).into()
-    }
-
-    fn carry_mod_64<F: PrimeField>(a: Blake2bWord, b: Blake2bWord) -> F {
-        let carry = (a.0 as u128 + b.0 as u128) / (1u128 << 64);
-        F::from(carry as u64)
-    }
 }
@@ -3,7 +3,6 @@ use crate::base_operations::types::blake2b_word::AssignedBlake2bWord;
 use crate::base_operations::types::byte::AssignedByte;
 use crate::base_operations::types::row::AssignedRow;
 use ff::PrimeField;
-use midnight_proofs::circuit::Value;
 
 /// This gate rotates the limbs of a number to the right and uses copy constrains to ensure that
 /// the rotation is correct. It's used in our circuit to implement 16-bit, 24-bit and 32-bit rotations.
@@ -18,22 +17,22 @@ impl LimbRotation {
     }
 
     /// This method receives a row of cells, and rotates the limbs to the right by the number
-    /// specified in the limbs_to_rotate_to_the_right parameter. It then constrains the output
-    /// to be the correct rotation of the input.
+    /// specified in the limbs_to_rotate_to_the_right parameter. The full number of the output is
+    /// the precomputed 'result_value'. It then constrains the output to be the correct rotation
+    /// of the input.
     /// For this method to work, the input_row must be the last row of the trace at the moment
     /// the method is called
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn generate_rotation_rows_from_input_row<F: PrimeField>(
         &self,
         region: &mut Region<'_, F>,
         offset: &mut usize,
         input_row: AssignedRow<F>,
         limbs_to_rotate_to_the_right: usize,
+        result_value: Value<Blake2bWord>,
         full_number_u64_column: Column<Advice>,
         limbs: [Column<Advice>; 8],
     ) -> Result<AssignedBlake2bWord<F>, Error> {
-        let result_value =
-            Self::right_rotation_value(input_row.full_number.value(), limbs_to_rotate_to_the_right);
-
         let result_cell = region
             .assign_advice(
                 || "Full number rotation output",
@@ -62,15 +61,4 @@ impl LimbRotation {
         *offset += 1;
         Ok(result_cell)
     }
-
-    /// Computes the actual value of the rotation of the number
-    fn right_rotation_value(
-        value: Value<Blake2bWord>,
-        limbs_to_rotate: usize,
-    ) -> Value<Blake2bWord> {
-        value.map(|input| {
-            let bits_to_rotate = limbs_to_rotate * 8;
-            rotate_right_field_element(input, bits_to_rotate)
-        })
-    }
 }
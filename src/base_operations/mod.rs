@@ -13,33 +13,24 @@ pub mod xor;
 pub mod generic_limb_rotation;
 pub mod rotate_63;
 
-/// Given a [Blake2bWord], it returns another [Blake2bWord] with the original word rotated to the
-/// right by 'rotation_degree' bits.
-fn rotate_right_field_element(value_to_rotate: Blake2bWord, rotation_degree: usize) -> Blake2bWord {
-    let value_to_rotate = value_to_rotate.0;
-    let rotation_degree = rotation_degree % 64;
-    let rotated_value = ((value_to_rotate as u128) >> rotation_degree)
-        | ((value_to_rotate as u128) << (64 - rotation_degree));
-    (rotated_value as u64).into()
-}
-
 /// Given an array of [AssignedNative] byte-values, it puts in the circuit a full row with those
-/// bytes in the limbs and the resulting full number in the first column.
+/// bytes in the limbs and the resulting full number in the first column. The value of the full
+/// number, which is the little endian word made from the bytes, is computed by the caller.
 /// WARNING: this method doesn't set any constraints. That's the responsibility of the caller.
 pub(crate) fn generate_row_from_assigned_bytes<F: PrimeField>(
     region: &mut Region<'_, F>,
     bytes: &[AssignedNative<F>; 8],
+    word_value: Value<Blake2bWord>,
     offset: usize,
     full_number_u64: Column<Advice>,
     limbs: [Column<Advice>; 8],
 ) -> Result<AssignedRow<F>, Error> {
-    // Compute the full number from the limbs
-    let full_number_cell = AssignedBlake2bWord::assign_advice_word_from_field(
+    let full_number_cell = AssignedBlake2bWord::assign_advice_word(
         region,
         "full number",
         full_number_u64,
         offset,
-        compute_full_value_u64_from_bytes(bytes),
+        word_value,
     )?;
 
     let mut assigned_limbs = vec![];
@@ -52,6 +43,7 @@ pub(crate) fn generate_row_from_assigned_bytes<F: PrimeField>(
             limbs[index],
             offset,
             byte_cell.clone(),
+            get_limb_from(word_value, index),
         )?;
         assigned_limbs.push(assigned_byte)
     }
@@ -59,20 +51,6 @@ pub(crate) fn generate_row_from_assigned_bytes<F: PrimeField>(
     Ok(AssignedRow::new(full_number_cell, assigned_limbs.try_into().unwrap()))
 }
 
-/// Given a list of limb values, it returns the full number value that the limbs build up to.
-fn compute_full_value_u64_from_bytes<F: PrimeField>(bytes: &[AssignedNative<F>; 8]) -> Value<F> {
-    let mut full_number = F::ZERO;
-    // We process the limbs from the most significant to the least significant
-    for byte_cell in bytes.iter().rev() {
-        byte_cell.value().and_then(|v| {
-            full_number *= F::from(256u64);
-            full_number += *v;
-            Value::<F>::unknown()
-        });
-    }
-    Value::known(full_number)
-}
-
 /// Given a cell with a 64-bit value, it creates a new row with the copied full number and the
 /// decomposition in 8-bit limbs.
 /// WARNING: this method doesn't set any constraints. That's the responsibility of the caller.
@@ -149,10 +127,8 @@ pub(crate) fn create_row_with_word_and_limbs<F: PrimeField>(
     let assigned_limbs: Vec<AssignedByte<F>> = limb_values
         .iter()
         .enumerate()
-        .map(|(i, limb)| {
-            AssignedByte::assign_advice_byte(region, "limb", limbs[i], offset, *limb).unwrap()
-        })
-        .collect::<Vec<_>>();
+        .map(|(i, limb)| AssignedByte::assign_advice_byte(region, "limb", limbs[i], offset, *limb))
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok(AssignedRow::new(full_number_cell, assigned_limbs.try_into().unwrap()))
 }
@@ -187,31 +163,50 @@ pub(crate) fn create_limb_decomposition_gate<F: PrimeField>(
     })
 }
 
+/// The lookup table used to range-check the 8-bit limbs.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum RangeTable {
+    /// A single column holding the values in [0, 255], filled by [populate_lookup_table()].
+    Range(TableColumn),
+    /// The three columns of the xor table (left, right, out), filled by
+    /// [xor::populate_xor_lookup_table()]. The rows (limb, 0, limb) of the table are exactly the
+    /// ones with limb in [0, 255].
+    Xor([TableColumn; 3]),
+}
+
 /// Creates the necessary lookups to constrain that all the limbs in a given row are in the
 /// range [0, 255].
 pub(crate) fn create_range_check_gate<F: PrimeField>(
     meta: &mut ConstraintSystem<F>,
-    t_range: TableColumn,
+    range_table: RangeTable,
     q_range: Selector,
     limbs: [Column<Advice>; 8],
 ) {
     for limb in limbs {
-        range_check_for_limb(meta, &limb, &q_range, &t_range);
+        range_check_for_limb(meta, &limb, &q_range, range_table);
     }
 }
 
-/// Creates the lookup of an 8-bit limb. It uses the [t-range] table, which is filled in the
-/// [populate_lookup_table()] method, and the [q_range], which is turned on whenever needed
+/// Creates the lookup of an 8-bit limb against the [range_table], with the [q_range] selector,
+/// which is turned on whenever needed. When q_range is off, the looked up value is 0 (or the
+/// tuple (0, 0, 0) for the xor table), which is in the table.
 fn range_check_for_limb<F: PrimeField>(
     meta: &mut ConstraintSystem<F>,
     limb: &Column<Advice>,
     q_range: &Selector,
-    t_range: &TableColumn,
+    range_table: RangeTable,
 ) {
     meta.lookup(format!("lookup limb {limb:?}"), |meta| {
         let limb: Expression<F> = meta.query_advice(*limb, Rotation::cur());
         let q_range = meta.query_selector(*q_range);
-        vec![(q_range * limb, *t_range)]
+        match range_table {
+            RangeTable::Range(t_range) => vec![(q_range * limb, t_range)],
+            RangeTable::Xor([t_xor_left, t_xor_right, t_xor_out]) => vec![
+                (q_range.clone() * limb.clone(), t_xor_left),
+                (Expression::Constant(F::ZERO), t_xor_right),
+                (q_range * limb, t_xor_out),
+            ],
+        }
     });
 }
 
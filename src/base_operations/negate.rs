@@ -9,7 +9,7 @@ use super::*;
 /// allows us to avoid making duplicate constraints over both input and result.
 #[derive(Clone, Debug)]
 pub(crate) struct NegateConfig {
-    q_negate: Selector,
+    pub(crate) q_negate: Selector,
 }
 
 impl NegateConfig {
@@ -38,13 +38,14 @@ impl NegateConfig {
     }
 
     /// This method receives a [AssignedBlake2bWord] and a [full_number_column] where it will be
-    /// copied. In the same column, the result is placed in the next row. The gate constrains the
-    /// result.
+    /// copied. In the same column, the precomputed result is placed in the next row. The gate
+    /// constrains the result.
     pub(crate) fn generate_rows_from_cell<F: PrimeField>(
         &self,
         region: &mut Region<'_, F>,
         offset: &mut usize,
         input: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
         full_number_column: Column<Advice>,
     ) -> Result<AssignedBlake2bWord<F>, Error> {
         self.q_negate.enable(region, *offset)?;
@@ -57,9 +58,6 @@ impl NegateConfig {
         )?;
         *offset += 1;
 
-        let result_value: Value<Blake2bWord> =
-            input.value().map(|input| Blake2bWord(u64::MAX) - input);
-
         let result_cell = region
             .assign_advice(|| "Negation output", full_number_column, *offset, || result_value)?
             .into();
@@ -57,19 +57,20 @@ impl Rotate63Config {
         }
     }
 
-    /// This method receives a [AssignedBlake2bWord] and a [full_number_u64] column where it will be
-    /// copied. In the same column, the result is placed in the next row. The gate constrains the
-    /// result.
+    /// This method receives a [AssignedBlake2bWord], which must be in the previous row of the
+    /// [full_number_u64] column, and the precomputed value of its rotation. The result is placed
+    /// in the current row. The gate constrains the result.
     pub(crate) fn generate_64_bit_rotation_from_cells<F: PrimeField>(
         &self,
         region: &mut Region<'_, F>,
         offset: &mut usize,
         input: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
         full_number_u64: Column<Advice>,
         limbs: [Column<Advice>; 8],
     ) -> Result<AssignedBlake2bWord<F>, Error> {
+        debug_assert_eq!(input.cell().row_offset + 1, *offset);
         self.q_rot63.enable(region, *offset)?;
-        let result_value = input.value().map(|input| rotate_right_field_element(input, 63));
 
         self.q_decompose.enable(region, *offset)?;
         self.q_range.enable(region, *offset)?;
@@ -1,6 +1,5 @@
 use super::*;
 use crate::base_operations::types::blake2b_word::AssignedBlake2bWord;
-use crate::base_operations::types::byte::Byte;
 use crate::base_operations::types::row::AssignedRow;
 
 /// This config handles the xor operation in the trace. Requires a representation in 8-bit limbs
@@ -43,65 +42,39 @@ impl XorConfig {
         &self,
         layouter: &mut impl Layouter<F>,
     ) -> Result<(), Error> {
-        layouter.assign_table(
-            || "xor check table",
-            |mut table| {
-                for left in 0..256 {
-                    for right in 0..256 {
-                        let index = left * 256 + right;
-                        let result = left ^ right;
-                        table.assign_cell(
-                            || "left_value",
-                            self.t_xor_left,
-                            index,
-                            || Value::known(F::from(left as u64)),
-                        )?;
-                        table.assign_cell(
-                            || "right_value",
-                            self.t_xor_right,
-                            index,
-                            || Value::known(F::from(right as u64)),
-                        )?;
-                        table.assign_cell(
-                            || "out_value",
-                            self.t_xor_out,
-                            index,
-                            || Value::known(F::from(result as u64)),
-                        )?;
-                    }
-                }
-                Ok(())
-            },
-        )?;
-        Ok(())
+        populate_xor_lookup_table(layouter, self.table())
+    }
+
+    /// The columns of the lookup table: left operand, right operand and result.
+    pub(crate) fn table(&self) -> [TableColumn; 3] {
+        [self.t_xor_left, self.t_xor_right, self.t_xor_out]
     }
 
     /// This method generates the xor rows in the trace. Copying both operands into new rows on the
-    /// trace and then performing the xor operation on the row limbs. Each limb of the result is
-    /// looked up in a table to check that it is the xor result of the corresponding limbs of the
-    /// operands
+    /// trace and then assigning the (precomputed) result of the xor operation. Each limb of the
+    /// result is looked up in a table to check that it is the xor result of the corresponding
+    /// limbs of the operands
     pub(crate) fn generate_xor_rows_from_cells<F: PrimeField>(
         &self,
         region: &mut Region<'_, F>,
         offset: &mut usize,
         lhs: &AssignedBlake2bWord<F>,
         rhs: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
     ) -> Result<AssignedRow<F>, Error> {
         self.q_xor.enable(region, *offset)?;
 
         // We only enable decomposition because the range-checks are performed by the lookups of the gate
         self.q_decompose.enable(region, *offset)?;
-        let first_operand_row =
-            generate_row_from_cell(region, rhs, *offset, self.full_number_u64, self.limbs)?;
+        generate_row_from_cell(region, rhs, *offset, self.full_number_u64, self.limbs)?;
         *offset += 1;
 
         // We only enable decomposition because the range-checks are performed by the lookups of the gate
         self.q_decompose.enable(region, *offset)?;
-        let second_operand_row =
-            generate_row_from_cell(region, lhs, *offset, self.full_number_u64, self.limbs)?;
+        generate_row_from_cell(region, lhs, *offset, self.full_number_u64, self.limbs)?;
         *offset += 1;
 
-        self.generate_xor_result_row(region, offset, &first_operand_row, &second_operand_row)
+        self.generate_xor_result_row(region, offset, result_value)
     }
 
     /// This is similar to generate_xor_rows_from_cells but it reuses the first operand of the
@@ -113,53 +86,34 @@ impl XorConfig {
         offset: &mut usize,
         first_operand_row: &AssignedRow<F>,
         second_operand: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
     ) -> Result<AssignedRow<F>, Error> {
+        debug_assert_eq!(first_operand_row.full_number.cell().row_offset + 1, *offset);
         // Since the first row is being reused, the selector must be enabled for offset - 1
         self.q_xor.enable(region, *offset - 1)?;
 
         // We only enable decomposition because the range-checks are performed by the lookups of the gate
         self.q_decompose.enable(region, *offset)?;
-        let second_operand_row = generate_row_from_cell(
-            region,
-            second_operand,
-            *offset,
-            self.full_number_u64,
-            self.limbs,
-        )?;
+        generate_row_from_cell(region, second_operand, *offset, self.full_number_u64, self.limbs)?;
         *offset += 1;
 
-        self.generate_xor_result_row(region, offset, first_operand_row, &second_operand_row)
+        self.generate_xor_result_row(region, offset, result_value)
     }
 
-    /// This method uses [create_row_with_word_and_limbs] which is a method that doesn't range
+    /// This method uses [generate_row_from_word_and_keep_row] which is a method that doesn't range
     /// check the limbs. This is on purpose, because those limbs will be range-checked by this
     /// gate when doing the lookups.
     fn generate_xor_result_row<F: PrimeField>(
         &self,
         region: &mut Region<'_, F>,
         offset: &mut usize,
-        first_operand_row: &AssignedRow<F>,
-        second_operand_row: &AssignedRow<F>,
+        result_value: Value<Blake2bWord>,
     ) -> Result<AssignedRow<F>, Error> {
-        let mut result_limb_values: Vec<Value<Byte>> = Vec::with_capacity(8);
-        for i in 0..8 {
-            let left = first_operand_row.limbs[i].clone();
-            let right = second_operand_row.limbs[i].clone();
-            let result_value = left.value().zip(right.value()).map(|(v0, v1)| v0 ^ v1);
-            result_limb_values.push(result_value)
-        }
-        let result_value = first_operand_row
-            .full_number
-            .value()
-            .zip(second_operand_row.full_number.value())
-            .map(|(v0, v1)| v0 ^ v1);
-
         // We only enable decomposition because the range-checks are performed by the lookups of the gate
         self.q_decompose.enable(region, *offset)?;
-        let result_row = create_row_with_word_and_limbs(
+        let result_row = generate_row_from_word_and_keep_row(
             region,
             result_value,
-            result_limb_values.try_into().unwrap(),
             *offset,
             self.full_number_u64,
             self.limbs,
@@ -168,17 +122,30 @@ impl XorConfig {
         Ok(result_row)
     }
 
+    /// Creates the xor lookups together with the columns of their table.
+    #[cfg(test)]
     pub(crate) fn configure<F: PrimeField>(
         meta: &mut ConstraintSystem<F>,
         limbs_8_bits: [Column<Advice>; 8],
         full_number_u64: Column<Advice>,
         limbs: [Column<Advice>; 8],
         q_decompose: Selector,
+    ) -> Self {
+        let table = std::array::from_fn(|_| meta.lookup_table_column());
+        Self::configure_with_table(meta, limbs_8_bits, full_number_u64, limbs, q_decompose, table)
+    }
+
+    /// Creates the xor lookups over a table whose columns are created by the caller. This way the
+    /// table can be used by other lookups that are created before this config.
+    pub(crate) fn configure_with_table<F: PrimeField>(
+        meta: &mut ConstraintSystem<F>,
+        limbs_8_bits: [Column<Advice>; 8],
+        full_number_u64: Column<Advice>,
+        limbs: [Column<Advice>; 8],
+        q_decompose: Selector,
+        [t_xor_left, t_xor_right, t_xor_out]: [TableColumn; 3],
     ) -> Self {
         let q_xor = meta.complex_selector();
-        let t_xor_left = meta.lookup_table_column();
-        let t_xor_right = meta.lookup_table_column();
-        let t_xor_out = meta.lookup_table_column();
 
         // We need to perform a lookup for each limb
         for limb in limbs_8_bits {
@@ -206,3 +173,40 @@ impl XorConfig {
         }
     }
 }
+
+/// Fills the xor table (left, right, out) with all the combinations of 8-bit operands.
+pub(crate) fn populate_xor_lookup_table<F: PrimeField>(
+    layouter: &mut impl Layouter<F>,
+    [t_xor_left, t_xor_right, t_xor_out]: [TableColumn; 3],
+) -> Result<(), Error> {
+    layouter.assign_table(
+        || "xor check table",
+        |mut table| {
+            for left in 0..256 {
+                for right in 0..256 {
+                    let index = left * 256 + right;
+                    let result = left ^ right;
+                    table.assign_cell(
+                        || "left_value",
+                        t_xor_left,
+                        index,
+                        || Value::known(F::from(left as u64)),
+                    )?;
+                    table.assign_cell(
+                        || "right_value",
+                        t_xor_right,
+                        index,
+                        || Value::known(F::from(right as u64)),
+                    )?;
+                    table.assign_cell(
+                        || "out_value",
+                        t_xor_out,
+                        index,
+                        || Value::known(F::from(result as u64)),
+                    )?;
+                }
+            }
+            Ok(())
+        },
+    )
+}
@@ -0,0 +1,331 @@
+//! Command-line tool to generate and verify proofs of Blake2b hashes with files on disk.
+//!
+//! The files it reads and writes are:
+//! * Params, verifying keys and proving keys: the midnight-proofs serialization of the KZG
+//!   params, the verifying key and the proving key, in the `RawBytes` format.
+//! * Message and key: the raw bytes to be hashed. An absent key file means an unkeyed hash.
+//! * Proof: the raw bytes of the proof transcript.
+//! * Digest: the public output of the circuit, as a hex string.
+//!
+//! The keys depend on the sizes of the message, key and output, so the files given to `prove`
+//! must have the sizes the keys were generated for.
+
+use blake2b_halo2::usage_utils::blake2b_circuit::{Blake2bCircuit, Blake2bInstanceMode};
+use blake2b_halo2::usage_utils::circuit_runner::Bls12CircuitRunner;
+#[cfg(feature = "dev-graph")]
+use blake2b_halo2::usage_utils::constraint_coverage::constraint_coverage;
+use blake2b_halo2::usage_utils::cost_report::cost_report;
+#[cfg(feature = "dev-graph")]
+use blake2b_halo2::usage_utils::layout::circuit_layout;
+use blake2b_halo2::usage_utils::vk_fingerprint::vk_fingerprint;
+use clap::{Parser, Subcommand};
+use midnight_curves::bls12_381::{Bls12, Fq};
+use midnight_proofs::circuit::Value;
+use midnight_proofs::poly::kzg::params::ParamsKZG;
+use midnight_proofs::utils::SerdeFormat;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+const FORMAT: SerdeFormat = SerdeFormat::RawBytes;
+
+#[derive(Parser, Debug)]
+#[command(name = "blake2b-halo2", version, about = "Prove and verify Blake2b hashes")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Write freshly generated KZG params, of the smallest size that fits the circuit for the
+    /// given message and key sizes. They come from an unsafe setup, only meant for testing.
+    Setup {
+        /// File to write the params to
+        #[arg(long)]
+        params: PathBuf,
+        /// Size of the message, in bytes
+        #[arg(long, default_value_t = 0)]
+        input_size: usize,
+        /// Size of the key, in bytes
+        #[arg(long, default_value_t = 0)]
+        key_size: usize,
+    },
+    /// Write the verifying and proving keys for the given message, key and output sizes
+    Keygen {
+        /// File to read the params from
+        #[arg(long)]
+        params: PathBuf,
+        /// Size of the message, in bytes
+        #[arg(long)]
+        input_size: usize,
+        /// Size of the key, in bytes
+        #[arg(long, default_value_t = 0)]
+        key_size: usize,
+        /// Size of the digest, in bytes
+        #[arg(long, default_value_t = 64)]
+        output_size: usize,
+        /// File to write the verifying key to
+        #[arg(long)]
+        vk: PathBuf,
+        /// File to write the proving key to
+        #[arg(long)]
+        pk: PathBuf,
+    },
+    /// Prove the hash of a message, writing the proof and the public digest
+    Prove {
+        /// File to read the params from
+        #[arg(long)]
+        params: PathBuf,
+        /// File to read the proving key from
+        #[arg(long)]
+        pk: PathBuf,
+        /// File with the message to hash
+        #[arg(long)]
+        message: PathBuf,
+        /// File with the key of the hash
+        #[arg(long)]
+        key: Option<PathBuf>,
+        /// Size of the digest, in bytes
+        #[arg(long, default_value_t = 64)]
+        output_size: usize,
+        /// File to write the proof to
+        #[arg(long)]
+        proof: PathBuf,
+        /// File to write the digest to
+        #[arg(long)]
+        digest: PathBuf,
+    },
+    /// Verify a proof against its public digest
+    Verify {
+        /// File to read the params from
+        #[arg(long)]
+        params: PathBuf,
+        /// File to read the verifying key from
+        #[arg(long)]
+        vk: PathBuf,
+        /// File to read the proof from
+        #[arg(long)]
+        proof: PathBuf,
+        /// File to read the digest from
+        #[arg(long)]
+        digest: PathBuf,
+    },
+    /// Print, as JSON, the resources the circuit needs for the given sizes
+    CostReport {
+        /// Size of the message, in bytes
+        #[arg(long, default_value_t = 0)]
+        input_size: usize,
+        /// Size of the key, in bytes
+        #[arg(long, default_value_t = 0)]
+        key_size: usize,
+        /// Size of the digest, in bytes
+        #[arg(long, default_value_t = 64)]
+        output_size: usize,
+    },
+    /// Print a table with the regions, advice cells, selectors and operation of every row of the
+    /// circuit for the given sizes
+    #[cfg(feature = "dev-graph")]
+    Layout {
+        /// Size of the message, in bytes
+        #[arg(long, default_value_t = 0)]
+        input_size: usize,
+        /// Size of the key, in bytes
+        #[arg(long, default_value_t = 0)]
+        key_size: usize,
+        /// Size of the digest, in bytes
+        #[arg(long, default_value_t = 64)]
+        output_size: usize,
+    },
+    /// Print the gates, lookups and copies that touch every kind of advice cell of the circuit for
+    /// the given sizes, and fail if some cell is touched by none of them
+    #[cfg(feature = "dev-graph")]
+    ConstraintCoverage {
+        /// Size of the message, in bytes
+        #[arg(long, default_value_t = 0)]
+        input_size: usize,
+        /// Size of the key, in bytes
+        #[arg(long, default_value_t = 0)]
+        key_size: usize,
+        /// Size of the digest, in bytes
+        #[arg(long, default_value_t = 64)]
+        output_size: usize,
+    },
+    /// Print, in hex, the fingerprint of the verifying key of the circuit for the given sizes
+    VkFingerprint {
+        /// Size of the message, in bytes
+        #[arg(long, default_value_t = 0)]
+        input_size: usize,
+        /// Size of the key, in bytes
+        #[arg(long, default_value_t = 0)]
+        key_size: usize,
+        /// Size of the digest, in bytes
+        #[arg(long, default_value_t = 64)]
+        output_size: usize,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse().command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Setup {
+            params,
+            input_size,
+            key_size,
+        } => setup(&params, input_size, key_size),
+        Command::Keygen {
+            params,
+            input_size,
+            key_size,
+            output_size,
+            vk,
+            pk,
+        } => keygen(&params, input_size, key_size, output_size, &vk, &pk),
+        Command::Prove {
+            params,
+            pk,
+            message,
+            key,
+            output_size,
+            proof,
+            digest,
+        } => prove(&params, &pk, &message, key.as_deref(), output_size, &proof, &digest),
+        Command::Verify {
+            params,
+            vk,
+            proof,
+            digest,
+        } => verify(&params, &vk, &proof, &digest),
+        Command::CostReport {
+            input_size,
+            key_size,
+            output_size,
+        } => {
+            println!("{}", cost_report(input_size, key_size, output_size)?.to_json()?);
+            Ok(())
+        }
+        #[cfg(feature = "dev-graph")]
+        Command::Layout {
+            input_size,
+            key_size,
+            output_size,
+        } => {
+            print!("{}", circuit_layout(input_size, key_size, output_size)?.to_table());
+            Ok(())
+        }
+        #[cfg(feature = "dev-graph")]
+        Command::ConstraintCoverage {
+            input_size,
+            key_size,
+            output_size,
+        } => {
+            let coverage = constraint_coverage(input_size, key_size, output_size)?;
+            print!("{}", coverage.to_table());
+            let unconstrained = coverage.unconstrained_cells().len();
+            if unconstrained > 0 {
+                return Err(format!(
+                    "{unconstrained} of {} advice cells are unconstrained",
+                    coverage.cells.len()
+                )
+                .into());
+            }
+            Ok(())
+        }
+        Command::VkFingerprint {
+            input_size,
+            key_size,
+            output_size,
+        } => {
+            println!("{}", hex::encode(vk_fingerprint(input_size, key_size, output_size)?));
+            Ok(())
+        }
+    }
+}
+
+fn setup(params_path: &Path, input_size: usize, key_size: usize) -> Result<(), Box<dyn Error>> {
+    let k = Blake2bCircuit::<Fq>::k_for(input_size, key_size);
+    let params = ParamsKZG::<Bls12>::unsafe_setup(k, &mut rand::thread_rng());
+    Bls12CircuitRunner::write_params(&params, params_path, FORMAT)?;
+    Ok(())
+}
+
+fn keygen(
+    params_path: &Path,
+    input_size: usize,
+    key_size: usize,
+    output_size: usize,
+    vk_path: &Path,
+    pk_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let params = Bls12CircuitRunner::read_params(params_path, FORMAT)?;
+    let circuit = Blake2bCircuit::for_sizes(input_size, key_size, output_size)?;
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params)?;
+    Bls12CircuitRunner::write_vk(&vk, vk_path, FORMAT)?;
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk)?;
+    Bls12CircuitRunner::write_pk(&pk, pk_path, FORMAT)?;
+    Ok(())
+}
+
+fn prove(
+    params_path: &Path,
+    pk_path: &Path,
+    message_path: &Path,
+    key_path: Option<&Path>,
+    output_size: usize,
+    proof_path: &Path,
+    digest_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let params = Bls12CircuitRunner::read_params(params_path, FORMAT)?;
+    let pk = Bls12CircuitRunner::read_pk(pk_path, FORMAT, Blake2bInstanceMode::Public)?;
+    let message = fs::read(message_path)?;
+    let key = key_path.map(fs::read).transpose()?.unwrap_or_default();
+
+    let circuit = Bls12CircuitRunner::create_circuit_for_inputs(
+        values_for(&message),
+        message.len(),
+        values_for(&key),
+        key.len(),
+        output_size,
+    )?;
+    let digest = blake2b_simd::Params::new().hash_length(output_size).key(&key).hash(&message);
+    let artifacts =
+        Bls12CircuitRunner::create_proof(&fields_for(digest.as_bytes()), circuit, &params, &pk)?;
+
+    fs::write(proof_path, artifacts.proof)?;
+    fs::write(digest_path, format!("{}\n", digest.to_hex()))?;
+    Ok(())
+}
+
+fn verify(
+    params_path: &Path,
+    vk_path: &Path,
+    proof_path: &Path,
+    digest_path: &Path,
+) -> Result<(), Box<dyn Error>> {
+    let params = Bls12CircuitRunner::read_params(params_path, FORMAT)?;
+    let vk = Bls12CircuitRunner::read_vk(vk_path, FORMAT, Blake2bInstanceMode::Public)?;
+    let proof = fs::read(proof_path)?;
+    let digest = hex::decode(fs::read_to_string(digest_path)?.trim())?;
+
+    Bls12CircuitRunner::verify_with_vk(&vk, &params, &fields_for(&digest), &proof)?;
+    println!("The proof is valid");
+    Ok(())
+}
+
+fn values_for(bytes: &[u8]) -> Vec<Value<Fq>> {
+    bytes.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect()
+}
+
+fn fields_for(bytes: &[u8]) -> Vec<Fq> {
+    bytes.iter().map(|byte| Fq::from(*byte as u64)).collect()
+}
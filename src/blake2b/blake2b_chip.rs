@@ -1,3 +1,4 @@
+use std::array;
 use std::marker::PhantomData;
 
 use crate::base_operations::addition_mod_64::AdditionMod64Config;
@@ -11,19 +12,45 @@ use crate::types::AssignedNative;
 use crate::base_operations::xor::XorConfig;
 use crate::base_operations::{
     create_limb_decomposition_gate, create_range_check_gate, generate_row_from_assigned_bytes,
-    populate_lookup_table,
+    generate_row_from_word_value, populate_lookup_table, RangeTable,
 };
 use crate::blake2b::blake2b_instructions::{Blake2bInstructions, ConstantCells};
+use crate::blake2b::compression_gadget::Blake2bCounter;
+use crate::blake2b::row_labels::RowLabels;
 use crate::blake2b::utils::{
     compute_processed_bytes_count_value_for_iteration, constrain_padding_cells_to_equal_zero,
     enforce_input_sizes, full_number_of_each_state_row, get_total_blocks_count,
     zeros_to_pad_in_current_block, ABCD, BLAKE2B_BLOCK_SIZE, IV_CONSTANTS, SIGMA,
 };
+use crate::error::Blake2bError;
+use crate::blake2b::witness::{Blake2bWitness, CompressionWitness, MixWitness};
 use crate::blake2b::NB_BLAKE2B_ADVICE_COLS;
+use crate::types::blake2b_word::Blake2bWord;
 use ff::{Field, PrimeField};
-use midnight_proofs::circuit::{Chip, Layouter, Region};
+use midnight_proofs::circuit::{Chip, Layouter, Region, Value};
 use midnight_proofs::plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector, TableColumn};
 
+/// Name of the region assigned by [Blake2bChip::hash] and
+/// [Blake2bChip::hash_with_precomputed_prefix].
+pub(crate) const HASH_REGION: &str = "single region";
+/// Rows at the beginning of the region of [Blake2bChip::hash]: one with the iv constants and one
+/// with the zero constant and the initial state[0].
+pub(crate) const CONSTANT_ROWS: usize = 2;
+/// Rows of an addition or a xor whose operands are both copied into the region: two operand rows
+/// and the result row. When the first operand is the result of the previous operation, its row
+/// is reused and the operation takes one row less.
+pub(crate) const BINARY_OPERATION_ROWS: usize = 3;
+/// Rows of a rotation, which only adds the rotated result row.
+pub(crate) const ROTATION_ROWS: usize = 1;
+/// Rows of the negation performed in the last compression.
+pub(crate) const NEGATE_ROWS: usize = 2;
+/// Rows of a mix: the first addition copies both operands, while the other 5 additions and the
+/// 4 xors reuse the previous result, and each xor is followed by a rotation.
+const MIX_ROWS: usize = BINARY_OPERATION_ROWS + 9 * (BINARY_OPERATION_ROWS - 1) + 4 * ROTATION_ROWS;
+/// Rows of a compression: the 16 words of the block, the constant state[12], 12 rounds of 8
+/// mixes and the 16 xors that compute the new global state.
+const COMPRESSION_ROWS: usize = 16 + 1 + 12 * 8 * MIX_ROWS + 16 * BINARY_OPERATION_ROWS;
+
 /// Selectors and columns for the blake2b chip implementation.
 #[derive(Clone, Debug)]
 pub struct Blake2bConfig {
@@ -40,7 +67,35 @@ pub struct Blake2bConfig {
     /// Decomposition selectors
     q_range: Selector,
     q_decompose: Selector,
-    t_range: TableColumn,
+    /// The 8-bit range table, only when it was created by the chip and so it has to be filled
+    /// by [load]. When the table is shared with other gadgets, its owner is the one who fills
+    /// it, and when the range checks use the xor table there is no such table.
+    t_range: Option<TableColumn>,
+}
+
+impl Blake2bConfig {
+    /// The selectors of the chip, along with the names of their fields.
+    pub(crate) fn named_selectors(&self) -> [(&'static str, Selector); 6] {
+        [
+            ("q_range", self.q_range),
+            ("q_decompose", self.q_decompose),
+            ("q_add", self.addition_config.q_add),
+            ("q_xor", self.xor_config.q_xor),
+            ("q_rot63", self.rotate_63_config.q_rot63),
+            ("q_negate", self.negate_config.q_negate),
+        ]
+    }
+}
+
+/// The lookup table used by the chip to range-check the 8-bit limbs.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RangeCheckTable {
+    /// A dedicated table column holding the values in [0, 255].
+    #[default]
+    Dedicated,
+    /// The xor table, looking up the tuples (limb, 0, limb). This saves one table column and its
+    /// assignment, at the cost of range-check lookups of three columns instead of one.
+    Xor,
 }
 
 /// This is the main chip for the Blake2b hash function. It is responsible for the entire hash computation.
@@ -52,6 +107,8 @@ pub struct Blake2bConfig {
 #[derive(Clone, Debug)]
 pub struct Blake2bChip<F> {
     config: Blake2bConfig,
+    /// Where the chip labels the rows of its region while it assigns them, if anywhere.
+    row_labels: Option<RowLabels>,
     _marker: PhantomData<F>,
 }
 
@@ -72,8 +129,13 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
     /// This optimization uses 2 tables:
     /// * A lookup table for range-checks of 8 bits: [0, 255]
     /// * A lookup table consisting of 3 columns that pre-computes the xor operation of 16 bits.
+    ///
+    /// The range-check table is skipped when it's shared with other gadgets or when the range
+    /// checks are performed with the xor table.
     fn populate_lookup_tables(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
-        self.populate_lookup_table_8(layouter)?;
+        if let Some(t_range) = self.config.t_range {
+            populate_lookup_table(layouter, t_range)?;
+        }
         self.populate_xor_lookup_table(layouter)
     }
 
@@ -89,6 +151,7 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
         let iv_constant_cells: [AssignedBlake2bWord<F>; 8] =
             self.assign_iv_constants_to_fixed_cells(region, advice_offset)?;
 
+        self.label_operation(*advice_offset, "zero and initial state[0]");
         let zero_constant = region.assign_advice_from_constant(
             || "zero",
             self.config.limbs[0],
@@ -135,9 +198,11 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
         offset: &mut usize,
         input: &[AssignedNative<F>],
         key: &[AssignedNative<F>],
+        prefix_len: usize,
         iv_constants: &[AssignedBlake2bWord<F>; 8],
         global_state: &mut [AssignedBlake2bWord<F>; 8],
         zero_constant_cell: AssignedNative<F>,
+        witness: &Value<Blake2bWitness>,
     ) -> Result<[AssignedByte<F>; 64], Error> {
         let input_size = input.len();
         let is_key_empty = key.is_empty();
@@ -155,12 +220,13 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
 
                 // This is an intermediate value in the Blake2b algorithm. It represents the amount
                 // of bytes processed so far.
-                let processed_bytes_count = compute_processed_bytes_count_value_for_iteration(
-                    i,
-                    is_last_block,
-                    input_size,
-                    is_key_empty,
-                );
+                let processed_bytes_count = prefix_len as u64
+                    + compute_processed_bytes_count_value_for_iteration(
+                        i,
+                        is_last_block,
+                        input_size,
+                        is_key_empty,
+                    );
 
                 let amount_of_zeros_to_pad =
                     zeros_to_pad_in_current_block(key, input_size, is_last_block, is_key_block);
@@ -176,10 +242,16 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
                     zero_constant_cell.clone(),
                 );
 
+                let compression_witness = witness.as_ref().map(|w| &w.compressions[i]);
+
+                if let Some(row_labels) = &self.row_labels {
+                    row_labels.set_block(Some(i));
+                }
                 let current_block_rows = self.block_words_from_bytes(
                     region,
                     offset,
                     current_block_values.try_into().unwrap(),
+                    compression_witness.map(|w| w.block),
                 )?;
 
                 constrain_padding_cells_to_equal_zero(
@@ -199,6 +271,7 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
                     current_block_cells,
                     processed_bytes_count,
                     is_last_block,
+                    compression_witness,
                 )
             })
             .last()
@@ -215,6 +288,7 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
         current_block: [AssignedBlake2bWord<F>; 16],
         processed_bytes_count: u64,
         is_last_block: bool,
+        witness: Value<&CompressionWitness>,
     ) -> Result<[AssignedByte<F>; 64], Error> {
         let mut state_vector: Vec<AssignedBlake2bWord<F>> = Vec::new();
         state_vector.extend_from_slice(global_state);
@@ -227,6 +301,7 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
         // and processed_bytes_count is public for both parties, the xor between both values
         // is also a constant.
         let new_state_12 = processed_bytes_count ^ IV_CONSTANTS[4];
+        self.label_operation(*row_offset, "v[12] = v[12] ^ t");
         state[12] = AssignedBlake2bWord::assign_fixed_word(
             region,
             "New state[12]",
@@ -237,34 +312,12 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
         *row_offset += 1;
 
         if is_last_block {
-            state[14] = self.not(&state[14], region, row_offset)?;
-        }
-
-        // Main loop
-        for i in 0..12 {
-            for j in 0..8 {
-                self.mix(
-                    [ABCD[j][0], ABCD[j][1], ABCD[j][2], ABCD[j][3]],
-                    current_block[SIGMA[i][2 * j]].clone(),
-                    current_block[SIGMA[i][2 * j + 1]].clone(),
-                    &mut state,
-                    region,
-                    row_offset,
-                )?;
-            }
+            self.label_operation(*row_offset, "v[14] = !v[14]");
+            let negated_value = witness.map(|w| w.negated_state_14);
+            state[14] = self.not(&state[14], negated_value, region, row_offset)?;
         }
 
-        let mut global_state_bytes: Vec<AssignedByte<F>> = Vec::new();
-        for i in 0..8 {
-            global_state[i] =
-                self.xor(&global_state[i], &state[i], region, row_offset)?.full_number;
-            let row = self.xor(&global_state[i], &state[i + 8], region, row_offset)?;
-            let mut row_limbs: Vec<_> = row.limbs.into();
-            global_state_bytes.append(&mut row_limbs);
-            global_state[i] = row.full_number;
-        }
-        let global_state_bytes_array = global_state_bytes.try_into().unwrap();
-        Ok(global_state_bytes_array)
+        self.compress_state(region, row_offset, global_state, state, current_block, witness)
     }
 
     fn mix(
@@ -275,41 +328,114 @@ impl<F: PrimeField> Blake2bInstructions<F> for Blake2bChip<F> {
         state: &mut [AssignedBlake2bWord<F>; 16],
         region: &mut Region<'_, F>,
         offset: &mut usize,
+        witness: Value<MixWitness>,
     ) -> Result<(), Error> {
         let v_a = &state[state_indexes[0]];
         let v_b = &state[state_indexes[1]];
         let v_c = &state[state_indexes[2]];
         let v_d = &state[state_indexes[3]];
 
+        let a_plus_b_values = witness.map(|w| w.a_plus_b);
+        let a_plus_b_carries = witness.map(|w| w.a_plus_b_carry);
+        let a_values = witness.map(|w| w.a);
+        let a_carries = witness.map(|w| w.a_carry);
+        let d_xor_a_values = witness.map(|w| w.d_xor_a);
+        let d_values = witness.map(|w| w.d);
+        let c_values = witness.map(|w| w.c);
+        let c_carries = witness.map(|w| w.c_carry);
+        let b_xor_c_values = witness.map(|w| w.b_xor_c);
+        let b_values = witness.map(|w| w.b);
+
         // v[a] = ((v[a] as u128 + v[b] as u128 + x as u128) % (1 << 64)) as u64;
-        let a_plus_b = self.add(v_a, v_b, region, offset)?;
-        let a = self.add_copying_one_parameter(&a_plus_b.full_number, &x, region, offset)?;
+        self.label_operation(*offset, "a = a + b");
+        let a_plus_b = self.add(
+            v_a,
+            v_b,
+            a_plus_b_values.map(|v| v[0]),
+            a_plus_b_carries.map(|v| v[0]),
+            region,
+            offset,
+        )?;
+        self.label_operation(*offset, "a = a + x");
+        let a = self.add_copying_one_parameter(
+            &a_plus_b.full_number,
+            &x,
+            a_values.map(|v| v[0]),
+            a_carries.map(|v| v[0]),
+            region,
+            offset,
+        )?;
 
         // v[d] = rotr_64(v[d] ^ v[a], 32);
-        let d_xor_a = self.xor_copying_one_parameter(&a, v_d, region, offset)?;
-        let d = self.rotate_right_32(d_xor_a, region, offset)?;
+        self.label_operation(*offset, "d = d ^ a");
+        let d_xor_a =
+            self.xor_copying_one_parameter(&a, v_d, d_xor_a_values.map(|v| v[0]), region, offset)?;
+        self.label_operation(*offset, "d = d >>> 32");
+        let d = self.rotate_right_32(d_xor_a, d_values.map(|v| v[0]), region, offset)?;
 
         // v[c] = ((v[c] as u128 + v[d] as u128) % (1 << 64)) as u64;
-        let c = self.add_copying_one_parameter(&d, v_c, region, offset)?;
+        self.label_operation(*offset, "c = c + d");
+        let c = self.add_copying_one_parameter(
+            &d,
+            v_c,
+            c_values.map(|v| v[0]),
+            c_carries.map(|v| v[0]),
+            region,
+            offset,
+        )?;
 
         // v[b] = rotr_64(v[b] ^ v[c], 24);
-        let b_xor_c = self.xor_copying_one_parameter(&c, v_b, region, offset)?;
-        let b = self.rotate_right_24(b_xor_c, region, offset)?;
+        self.label_operation(*offset, "b = b ^ c");
+        let b_xor_c =
+            self.xor_copying_one_parameter(&c, v_b, b_xor_c_values.map(|v| v[0]), region, offset)?;
+        self.label_operation(*offset, "b = b >>> 24");
+        let b = self.rotate_right_24(b_xor_c, b_values.map(|v| v[0]), region, offset)?;
 
         // v[a] = ((v[a] as u128 + v[b] as u128 + y as u128) % (1 << 64)) as u64;
-        let a_plus_b = self.add_copying_one_parameter(&b, &a.full_number, region, offset)?;
-        let a = self.add_copying_one_parameter(&a_plus_b.full_number, &y, region, offset)?;
+        self.label_operation(*offset, "a = a + b");
+        let a_plus_b = self.add_copying_one_parameter(
+            &b,
+            &a.full_number,
+            a_plus_b_values.map(|v| v[1]),
+            a_plus_b_carries.map(|v| v[1]),
+            region,
+            offset,
+        )?;
+        self.label_operation(*offset, "a = a + y");
+        let a = self.add_copying_one_parameter(
+            &a_plus_b.full_number,
+            &y,
+            a_values.map(|v| v[1]),
+            a_carries.map(|v| v[1]),
+            region,
+            offset,
+        )?;
 
         // v[d] = rotr_64(v[d] ^ v[a], 16);
-        let d_xor_a = self.xor_copying_one_parameter(&a, &d, region, offset)?;
-        let d = self.rotate_right_16(d_xor_a, region, offset)?;
+        self.label_operation(*offset, "d = d ^ a");
+        let d_xor_a =
+            self.xor_copying_one_parameter(&a, &d, d_xor_a_values.map(|v| v[1]), region, offset)?;
+        self.label_operation(*offset, "d = d >>> 16");
+        let d = self.rotate_right_16(d_xor_a, d_values.map(|v| v[1]), region, offset)?;
 
         // v[c] = ((v[c] as u128 + v[d] as u128) % (1 << 64)) as u64;
-        let c = self.add_copying_one_parameter(&d, &c.full_number, region, offset)?;
+        self.label_operation(*offset, "c = c + d");
+        let c = self.add_copying_one_parameter(
+            &d,
+            &c.full_number,
+            c_values.map(|v| v[1]),
+            c_carries.map(|v| v[1]),
+            region,
+            offset,
+        )?;
 
         // v[b] = rotr_64(v[b] ^ v[c], 63);
-        let b_xor_c = self.xor_copying_one_parameter(&c, &b, region, offset)?;
-        let b = self.rotate_right_63(b_xor_c.full_number, region, offset)?;
+        self.label_operation(*offset, "b = b ^ c");
+        let b_xor_c =
+            self.xor_copying_one_parameter(&c, &b, b_xor_c_values.map(|v| v[1]), region, offset)?;
+        self.label_operation(*offset, "b = b >>> 63");
+        let b =
+            self.rotate_right_63(b_xor_c.full_number, b_values.map(|v| v[1]), region, offset)?;
 
         state[state_indexes[0]] = a.full_number;
         state[state_indexes[1]] = b;
@@ -325,10 +451,18 @@ impl<F: PrimeField> Blake2bChip<F> {
     pub fn new(config: &Blake2bConfig) -> Self {
         Self {
             config: config.clone(),
+            row_labels: None,
             _marker: PhantomData,
         }
     }
 
+    /// The same chip, recording the block, round, mix and operation of every row of the region
+    /// of the hash in 'row_labels' while it assigns them.
+    pub(crate) fn with_row_labels(mut self, row_labels: RowLabels) -> Self {
+        self.row_labels = Some(row_labels);
+        self
+    }
+
     /// Configuration of the circuit, this includes initialization of all the necessary configs.
     /// It should be called in the configuration of the user circuit before instantiating the
     /// Blake2b gadget.
@@ -340,6 +474,24 @@ impl<F: PrimeField> Blake2bChip<F> {
         constants: Column<Fixed>,
         full_number_u64: Column<Advice>,
         limbs: [Column<Advice>; NB_BLAKE2B_ADVICE_COLS - 1],
+    ) -> <Self as Chip<F>>::Config {
+        Self::configure_with_range_check_table(
+            meta,
+            constants,
+            full_number_u64,
+            limbs,
+            RangeCheckTable::Dedicated,
+        )
+    }
+
+    /// Same as [configure], but choosing the table used for the range checks of the limbs. With
+    /// [RangeCheckTable::Xor] the chip doesn't create the 8-bit range table.
+    pub fn configure_with_range_check_table(
+        meta: &mut ConstraintSystem<F>,
+        constants: Column<Fixed>,
+        full_number_u64: Column<Advice>,
+        limbs: [Column<Advice>; NB_BLAKE2B_ADVICE_COLS - 1],
+        range_check_table: RangeCheckTable,
     ) -> <Self as Chip<F>>::Config {
         // Enabling column properties.
         meta.enable_constant(constants);
@@ -348,14 +500,57 @@ impl<F: PrimeField> Blake2bChip<F> {
             meta.enable_equality(limb);
         }
 
+        let t_range = match range_check_table {
+            RangeCheckTable::Dedicated => Some(meta.lookup_table_column()),
+            RangeCheckTable::Xor => None,
+        };
+        Self::configure_gates(meta, full_number_u64, limbs, t_range, true)
+    }
+
+    /// Configuration of the chip over columns that are shared with other gadgets of the
+    /// circuit, e.g. the ones of midnight-circuits' native gadgets. Unlike [configure], it
+    /// doesn't create any advice column nor the 8-bit range table, and it doesn't change the
+    /// properties of the given columns. The caller is responsible for:
+    /// * enabling equality on 'full_number_u64' and all the 'limbs',
+    /// * enabling at least one fixed column for constants,
+    /// * filling 'range_table' with exactly the values in [0, 255], since [load] won't do it.
+    ///
+    /// The chip adds the following to the constraint system:
+    /// * Selectors: q_decompose, q_range, q_add, q_xor, q_rot63 and q_negate, all complex.
+    /// * Gates: "decompose in 8 bit words" (q_decompose), "sum mod 2 ^ 64" (q_add, which uses
+    ///   'limbs[0]' for the carry), "rotate right 63" (q_rot63) and "negate" (q_negate).
+    /// * Lookups: one range check per limb into 'range_table' (q_range), and one xor lookup per
+    ///   limb (q_xor) into three new table columns, which are filled by [load].
+    pub fn configure_with_shared_columns(
+        meta: &mut ConstraintSystem<F>,
+        full_number_u64: Column<Advice>,
+        limbs: [Column<Advice>; NB_BLAKE2B_ADVICE_COLS - 1],
+        range_table: TableColumn,
+    ) -> <Self as Chip<F>>::Config {
+        Self::configure_gates(meta, full_number_u64, limbs, Some(range_table), false)
+    }
+
+    /// Creates the selectors, gates and lookups of the chip over the given columns. The limbs are
+    /// range-checked against 't_range', or against the xor table if it's [None].
+    fn configure_gates(
+        meta: &mut ConstraintSystem<F>,
+        full_number_u64: Column<Advice>,
+        limbs: [Column<Advice>; NB_BLAKE2B_ADVICE_COLS - 1],
+        t_range: Option<TableColumn>,
+        owns_range_table: bool,
+    ) -> <Self as Chip<F>>::Config {
         // Gate that checks if the 8-bit limb decomposition is correct
         let q_decompose = meta.complex_selector();
         create_limb_decomposition_gate(meta, q_decompose, full_number_u64, limbs);
 
         // Range-check lookups
         let q_range = meta.complex_selector();
-        let t_range = meta.lookup_table_column();
-        create_range_check_gate(meta, t_range, q_range, limbs);
+        let xor_table = array::from_fn(|_| meta.lookup_table_column());
+        let range_table = match t_range {
+            Some(t_range) => RangeTable::Range(t_range),
+            None => RangeTable::Xor(xor_table),
+        };
+        create_range_check_gate(meta, range_table, q_range, limbs);
 
         // Config that is the same for every optimization
         let rotate_63_config =
@@ -367,7 +562,14 @@ impl<F: PrimeField> Blake2bChip<F> {
         // For the carry column we'll reuse the first limb column for optimization reasons
         let addition_config =
             AdditionMod64Config::configure(meta, full_number_u64, limbs[0], q_decompose, q_range);
-        let xor_config = XorConfig::configure(meta, limbs, full_number_u64, limbs, q_decompose);
+        let xor_config = XorConfig::configure_with_table(
+            meta,
+            limbs,
+            full_number_u64,
+            limbs,
+            q_decompose,
+            xor_table,
+        );
 
         Blake2bConfig {
             addition_config,
@@ -379,13 +581,34 @@ impl<F: PrimeField> Blake2bChip<F> {
             limbs,
             q_range,
             q_decompose,
-            t_range,
+            t_range: t_range.filter(|_| owns_range_table),
         }
     }
 
-    /// Loading the tables used in the chip.
-    pub fn load(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
-        self.populate_lookup_tables(layouter)
+    /// Loading the tables used in the chip. The 8-bit range table is only filled if it was
+    /// created by the chip.
+    pub fn load(&self, layouter: &mut impl Layouter<F>) -> Result<(), Blake2bError> {
+        Ok(self.populate_lookup_tables(layouter)?)
+    }
+
+    /// Number of rows of the region assigned by [hash] for an input of 'input_len' bytes and a
+    /// key of 'key_len' bytes. It doesn't depend on the output size. The tables filled by [load]
+    /// need [crate::blake2b::NB_BLAKE2B_TABLE_ROWS] rows on their own, in their own columns.
+    pub fn rows_required(input_len: usize, key_len: usize) -> usize {
+        let input_blocks = input_len.div_ceil(BLAKE2B_BLOCK_SIZE);
+        let total_blocks = get_total_blocks_count(input_blocks, input_len == 0, key_len == 0);
+        CONSTANT_ROWS + total_blocks * COMPRESSION_ROWS + NEGATE_ROWS
+    }
+
+    /// Number of rows of the region assigned by [Self::hash_with_precomputed_prefix] for a suffix
+    /// of 'suffix_len' bytes: the prefix state takes one more constant row than the initial state.
+    pub fn rows_required_with_precomputed_prefix(suffix_len: usize) -> usize {
+        Self::rows_required(suffix_len, 0) + 1
+    }
+
+    /// Number of rows added to the region of [hash] by each block of the input and key.
+    pub fn rows_per_block() -> usize {
+        COMPRESSION_ROWS
     }
 }
 
@@ -398,14 +621,14 @@ impl<F: PrimeField> Blake2bChip<F> {
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<[AssignedBlake2bWord<F>; 8], Error> {
+        self.label_operation(*offset, "iv constants");
         let ret: [AssignedBlake2bWord<F>; 8] = IV_CONSTANTS
             .iter()
             .enumerate()
             .map(|(index, constant)| {
                 self.assign_limb_constant_u64(region, offset, "iv constants", *constant, index)
-                    .unwrap()
             })
-            .collect::<Vec<AssignedBlake2bWord<F>>>()
+            .collect::<Result<Vec<AssignedBlake2bWord<F>>, Error>>()?
             .try_into()
             .unwrap();
         *offset += 1;
@@ -418,6 +641,7 @@ impl<F: PrimeField> Blake2bChip<F> {
     fn not(
         &self,
         input_cell: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedBlake2bWord<F>, Error> {
@@ -425,6 +649,7 @@ impl<F: PrimeField> Blake2bChip<F> {
             region,
             offset,
             input_cell,
+            result_value,
             self.config.full_number_u64,
         )
     }
@@ -436,10 +661,11 @@ impl<F: PrimeField> Blake2bChip<F> {
         &self,
         lhs: &AssignedBlake2bWord<F>,
         rhs: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedRow<F>, Error> {
-        self.config.xor_config.generate_xor_rows_from_cells(region, offset, lhs, rhs)
+        self.config.xor_config.generate_xor_rows_from_cells(region, offset, lhs, rhs, result_value)
     }
 
     /// Addition operation. It's performed over two assigned blake2b words. Is one of the most
@@ -449,6 +675,8 @@ impl<F: PrimeField> Blake2bChip<F> {
         &self,
         lhs: &AssignedBlake2bWord<F>,
         rhs: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
+        carry_value: Value<bool>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedRow<F>, Error> {
@@ -460,6 +688,8 @@ impl<F: PrimeField> Blake2bChip<F> {
                 offset,
                 lhs,
                 rhs,
+                result_value,
+                carry_value,
                 false,
                 self.config.full_number_u64,
                 self.config.limbs,
@@ -474,6 +704,7 @@ impl<F: PrimeField> Blake2bChip<F> {
     fn rotate_right_63(
         &self,
         input: AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedBlake2bWord<F>, Error> {
@@ -481,6 +712,7 @@ impl<F: PrimeField> Blake2bChip<F> {
             region,
             offset,
             &input,
+            result_value,
             self.config.full_number_u64,
             self.config.limbs,
         )
@@ -492,6 +724,7 @@ impl<F: PrimeField> Blake2bChip<F> {
     fn rotate_right_16(
         &self,
         input_row: AssignedRow<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedBlake2bWord<F>, Error> {
@@ -500,6 +733,7 @@ impl<F: PrimeField> Blake2bChip<F> {
             offset,
             input_row,
             2,
+            result_value,
             self.config.full_number_u64,
             self.config.limbs,
         )
@@ -511,6 +745,7 @@ impl<F: PrimeField> Blake2bChip<F> {
     fn rotate_right_24(
         &self,
         input_row: AssignedRow<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedBlake2bWord<F>, Error> {
@@ -519,6 +754,7 @@ impl<F: PrimeField> Blake2bChip<F> {
             offset,
             input_row,
             3,
+            result_value,
             self.config.full_number_u64,
             self.config.limbs,
         )
@@ -530,6 +766,7 @@ impl<F: PrimeField> Blake2bChip<F> {
     fn rotate_right_32(
         &self,
         input_row: AssignedRow<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedBlake2bWord<F>, Error> {
@@ -538,6 +775,7 @@ impl<F: PrimeField> Blake2bChip<F> {
             offset,
             input_row,
             4,
+            result_value,
             self.config.full_number_u64,
             self.config.limbs,
         )
@@ -554,6 +792,7 @@ impl<F: PrimeField> Blake2bChip<F> {
         &self,
         previous_operand: &AssignedRow<F>,
         cell_to_copy: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedRow<F>, Error> {
@@ -562,6 +801,7 @@ impl<F: PrimeField> Blake2bChip<F> {
             offset,
             previous_operand,
             cell_to_copy,
+            result_value,
         )
     }
 
@@ -572,6 +812,8 @@ impl<F: PrimeField> Blake2bChip<F> {
         &self,
         previous_cell: &AssignedBlake2bWord<F>,
         cell_to_copy: &AssignedBlake2bWord<F>,
+        result_value: Value<Blake2bWord>,
+        carry_value: Value<bool>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedRow<F>, Error> {
@@ -583,6 +825,8 @@ impl<F: PrimeField> Blake2bChip<F> {
                 offset,
                 previous_cell,
                 cell_to_copy,
+                result_value,
+                carry_value,
                 true, // Uses the optimization
                 self.config.full_number_u64,
                 self.config.limbs,
@@ -590,22 +834,18 @@ impl<F: PrimeField> Blake2bChip<F> {
             .0)
     }
 
-    /// Fills the 8-bit range-check lookup table
-    fn populate_lookup_table_8(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
-        populate_lookup_table(layouter, self.config.t_range)
-    }
-
-    /// The xor lookup table is created by the [XorConfig], since it establishes the lookups over it.
+    /// The xor lookup table is filled by the [XorConfig], since it establishes the lookups over it.
     fn populate_xor_lookup_table(&self, layouter: &mut impl Layouter<F>) -> Result<(), Error> {
         self.config.xor_config.populate_xor_lookup_table(layouter)
     }
 
     /// Given an array of [AssignedNative] byte-values, it puts in the circuit a full row with those
-    /// bytes in the limbs and the resulting full number in the first column. The resulting values
-    /// are range-checked by the circuit.
+    /// bytes in the limbs and the resulting full number in the first column, whose value is
+    /// precomputed in 'word_value'. The resulting values are range-checked by the circuit.
     fn new_row_from_assigned_bytes(
         &self,
         bytes: &[AssignedNative<F>; 8],
+        word_value: Value<Blake2bWord>,
         region: &mut Region<'_, F>,
         offset: &mut usize,
     ) -> Result<AssignedRow<F>, Error> {
@@ -614,6 +854,7 @@ impl<F: PrimeField> Blake2bChip<F> {
         let ret = generate_row_from_assigned_bytes(
             region,
             bytes,
+            word_value,
             *offset,
             self.config.full_number_u64,
             self.config.limbs,
@@ -625,16 +866,21 @@ impl<F: PrimeField> Blake2bChip<F> {
     /// This method is used when building the block words from the input bytes. It receives a list
     /// of 128 [AssignedNative] bytes that still haven't been range-checked and returns a list of
     /// 16 [AssignedRow] putted in the trace, range-checked and ready for use in the algorithm.
+    /// The values of the words are taken from the precomputed 'block_words'.
     fn block_words_from_bytes(
         &self,
         region: &mut Region<'_, F>,
         offset: &mut usize,
         block: [AssignedNative<F>; 128],
+        block_words: Value<[Blake2bWord; 16]>,
     ) -> Result<[AssignedRow<F>; 16], Error> {
         let mut current_block_rows_vector: Vec<AssignedRow<F>> = Vec::new();
         for i in 0..16 {
             let bytes: &[AssignedNative<F>; 8] = block[i * 8..(i + 1) * 8].try_into().unwrap();
-            let current_row_cells = self.new_row_from_assigned_bytes(bytes, region, offset)?;
+            let word_value = block_words.map(|words| words[i]);
+            self.label_operation(*offset, format!("m[{i}]"));
+            let current_row_cells =
+                self.new_row_from_assigned_bytes(bytes, word_value, region, offset)?;
             current_block_rows_vector.push(current_row_cells);
         }
         let current_block_rows = current_block_rows_vector.try_into().unwrap();
@@ -671,6 +917,83 @@ impl<F: PrimeField> Blake2bChip<F> {
         }
     }
 
+    /// The rounds of a compression over the 16-word 'state', which already holds the counter and
+    /// the finalization flags, followed by the xors that update the chaining value in
+    /// 'global_state'. It returns the bytes of the new chaining value.
+    fn compress_state(
+        &self,
+        region: &mut Region<'_, F>,
+        row_offset: &mut usize,
+        global_state: &mut [AssignedBlake2bWord<F>; 8],
+        mut state: [AssignedBlake2bWord<F>; 16],
+        current_block: [AssignedBlake2bWord<F>; 16],
+        witness: Value<&CompressionWitness>,
+    ) -> Result<[AssignedByte<F>; 64], Error> {
+        // Main loop
+        for i in 0..12 {
+            for j in 0..8 {
+                if let Some(row_labels) = &self.row_labels {
+                    row_labels.set_mix(Some((i, j)));
+                }
+                self.mix(
+                    [ABCD[j][0], ABCD[j][1], ABCD[j][2], ABCD[j][3]],
+                    current_block[SIGMA[i][2 * j]].clone(),
+                    current_block[SIGMA[i][2 * j + 1]].clone(),
+                    &mut state,
+                    region,
+                    row_offset,
+                    witness.map(|w| w.mixes[i * 8 + j]),
+                )?;
+            }
+        }
+
+        if let Some(row_labels) = &self.row_labels {
+            row_labels.set_mix(None);
+        }
+
+        let mut global_state_bytes: Vec<AssignedByte<F>> = Vec::new();
+        for i in 0..8 {
+            self.label_operation(*row_offset, format!("h[{i}] = h[{i}] ^ v[{i}]"));
+            let state_xor_value = witness.map(|w| w.state_xor[i]);
+            global_state[i] = self
+                .xor(&global_state[i], &state[i], state_xor_value, region, row_offset)?
+                .full_number;
+            self.label_operation(*row_offset, format!("h[{i}] = h[{i}] ^ v[{}]", i + 8));
+            let new_state_value = witness.map(|w| w.new_state[i]);
+            let row =
+                self.xor(&global_state[i], &state[i + 8], new_state_value, region, row_offset)?;
+            let mut row_limbs: Vec<_> = row.limbs.into();
+            global_state_bytes.append(&mut row_limbs);
+            global_state[i] = row.full_number;
+        }
+        let global_state_bytes_array = global_state_bytes.try_into().unwrap();
+        Ok(global_state_bytes_array)
+    }
+
+    /// Copies a 64-bit word given as an [AssignedNative] into a new row with its limbs, which are
+    /// range-checked and tied to the word by the decomposition gate. 'value' is the word held by
+    /// the cell, computed natively by the caller.
+    fn range_checked_word(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: &mut usize,
+        word: &AssignedNative<F>,
+        value: Value<Blake2bWord>,
+    ) -> Result<AssignedBlake2bWord<F>, Error> {
+        self.config.q_decompose.enable(region, *offset)?;
+        self.config.q_range.enable(region, *offset)?;
+        let row = generate_row_from_word_value(
+            region,
+            value,
+            *offset,
+            self.config.full_number_u64,
+            self.config.limbs,
+        )?;
+        region.constrain_equal(word.cell(), row.full_number.cell())?;
+        *offset += 1;
+        Ok(row.full_number)
+    }
+
     /// Assigns an u64 constant to trace[row_offset][limbs[limb_index]] cell.
     fn assign_limb_constant_u64(
         &self,
@@ -688,22 +1011,37 @@ impl<F: PrimeField> Blake2bChip<F> {
             constant.into(),
         )
     }
+
+    /// Records that the rows from 'offset' on belong to 'operation', when the chip labels its
+    /// rows.
+    fn label_operation(&self, offset: usize, operation: impl Into<String>) {
+        if let Some(row_labels) = &self.row_labels {
+            row_labels.start_operation(offset, operation);
+        }
+    }
 }
 
 impl<F: PrimeField> Blake2bChip<F> {
     /// Main method of the chip. The 'input' and 'key' cells should be filled with byte values.
+    /// It fails if the output or key sizes are not valid, or if any of the known input and key
+    /// values is not a byte.
     pub fn hash(
         &self,
         layouter: &mut impl Layouter<F>,
         input: &[AssignedNative<F>],
         key: &[AssignedNative<F>],
         output_size: usize,
-    ) -> Result<[AssignedByte<F>; 64], Error> {
-        enforce_input_sizes(output_size, key.len());
+    ) -> Result<[AssignedByte<F>; 64], Blake2bError> {
+        enforce_input_sizes(output_size, key.len())?;
+        // Every value of the trace is computed natively before the region is assigned
+        let witness = Blake2bWitness::from_assigned_bytes(input, key, output_size)?;
         // All the computation is performed inside a single region
-        layouter.assign_region(
-            || "single region",
+        let result = layouter.assign_region(
+            || HASH_REGION,
             |mut region| {
+                if let Some(row_labels) = &self.row_labels {
+                    row_labels.enter_region(HASH_REGION);
+                }
                 // Initialize in 0 the offset for the advice cells in the region
                 let mut advice_offset: usize = 0;
 
@@ -723,11 +1061,309 @@ impl<F: PrimeField> Blake2bChip<F> {
                     &mut advice_offset,
                     input,
                     key,
+                    0,
                     &iv_constant_cells,
                     &mut initial_global_state,
                     zero_constant,
+                    &witness,
+                )
+            },
+        )?;
+        Ok(result)
+    }
+
+    /// Continues a hash whose first 'prefix_len' bytes were compressed outside the circuit, as
+    /// when they are a public constant. 'prefix_state' is the chaining value after those bytes,
+    /// see [Self::precompute_prefix_state], and 'prefix_len' counts the key block if the prefix
+    /// was keyed. The digest is the one of the prefix followed by 'suffix', for the output size
+    /// the prefix state was computed for. Only the compressions of 'suffix' are proven, so the
+    /// circuit trusts 'prefix_state' to be right. It fails if 'prefix_len' is not a multiple of
+    /// the block size or if 'suffix' is empty, since the last block is never part of the prefix.
+    pub fn hash_with_precomputed_prefix(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        prefix_state: [u64; 8],
+        prefix_len: usize,
+        suffix: &[AssignedNative<F>],
+    ) -> Result<[AssignedByte<F>; 64], Blake2bError> {
+        if !prefix_len.is_multiple_of(BLAKE2B_BLOCK_SIZE) {
+            return Err(Blake2bError::InvalidPrefixLength(prefix_len));
+        }
+        if suffix.is_empty() {
+            return Err(Blake2bError::EmptySuffix);
+        }
+        let witness = Blake2bWitness::from_assigned_suffix(prefix_state, prefix_len, suffix)?;
+        let result = layouter.assign_region(
+            || HASH_REGION,
+            |mut region| {
+                if let Some(row_labels) = &self.row_labels {
+                    row_labels.enter_region(HASH_REGION);
+                }
+                let mut advice_offset: usize = 0;
+
+                let iv_constant_cells =
+                    self.assign_iv_constants_to_fixed_cells(&mut region, &mut advice_offset)?;
+                // The prefix state is assigned as constants, in the same way as the iv
+                self.label_operation(advice_offset, "prefix state");
+                let mut global_state: [AssignedBlake2bWord<F>; 8] = prefix_state
+                    .iter()
+                    .enumerate()
+                    .map(|(index, word)| {
+                        self.assign_limb_constant_u64(
+                            &mut region,
+                            &advice_offset,
+                            "prefix state",
+                            *word,
+                            index,
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()
+                    .unwrap();
+                advice_offset += 1;
+                self.label_operation(advice_offset, "zero");
+                let zero_constant = region.assign_advice_from_constant(
+                    || "zero",
+                    self.config.limbs[0],
+                    advice_offset,
+                    F::from(0),
+                )?;
+                advice_offset += 1;
+
+                self.perform_blake2b_iterations(
+                    &mut region,
+                    &mut advice_offset,
+                    suffix,
+                    &[],
+                    prefix_len,
+                    &iv_constant_cells,
+                    &mut global_state,
+                    zero_constant,
+                    &witness,
                 )
             },
+        )?;
+        Ok(result)
+    }
+
+    /// Packs the bytes of a digest into 64-bit words, 8 bytes per word in little-endian order, so
+    /// the digest takes 8 times fewer instances. When the number of bytes is not a multiple of 8,
+    /// the last word is padded with zero bytes. The bytes were range-checked by the hash, and the
+    /// decomposition gate ties every word to its copies of them.
+    pub fn pack_digest(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        digest: &[AssignedByte<F>],
+    ) -> Result<Vec<AssignedNative<F>>, Blake2bError> {
+        let words = layouter.assign_region(
+            || "packed digest",
+            |mut region| {
+                digest
+                    .chunks(8)
+                    .enumerate()
+                    .map(|(offset, bytes)| {
+                        self.config.q_decompose.enable(&mut region, offset)?;
+                        let mut word_value = Value::known(0u64);
+                        for (index, limb) in self.config.limbs.iter().enumerate() {
+                            let byte_value = match bytes.get(index) {
+                                Some(byte) => AssignedByte::copy_advice_byte(
+                                    &mut region,
+                                    "packed digest byte",
+                                    *limb,
+                                    offset,
+                                    byte.clone(),
+                                )?
+                                .value()
+                                .map(|byte| byte.0),
+                                None => {
+                                    region.assign_advice_from_constant(
+                                        || "packed digest padding",
+                                        *limb,
+                                        offset,
+                                        F::ZERO,
+                                    )?;
+                                    Value::known(0)
+                                }
+                            };
+                            word_value = word_value
+                                .zip(byte_value)
+                                .map(|(word, byte)| word | (byte as u64) << (8 * index));
+                        }
+                        region.assign_advice(
+                            || "packed digest word",
+                            self.config.full_number_u64,
+                            offset,
+                            || word_value.map(F::from),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, Error>>()
+            },
+        )?;
+        Ok(words)
+    }
+
+    /// Natively computes the chaining value after compressing 'key', if any, and 'prefix', for a
+    /// hash with an output of 'output_size' bytes. This is the 'prefix_state' of
+    /// [Self::hash_with_precomputed_prefix], with a 'prefix_len' of the length of 'prefix' plus
+    /// one block for a non-empty key. It fails if the key or output sizes are not valid or if
+    /// 'prefix' is not made of full blocks.
+    pub fn precompute_prefix_state(
+        prefix: &[u8],
+        key: &[u8],
+        output_size: usize,
+    ) -> Result<[u64; 8], Blake2bError> {
+        enforce_input_sizes(output_size, key.len())?;
+        if !prefix.len().is_multiple_of(BLAKE2B_BLOCK_SIZE) {
+            return Err(Blake2bError::InvalidPrefixLength(prefix.len()));
+        }
+        Ok(Blake2bWitness::prefix_state(prefix, key, output_size))
+    }
+}
+
+/// The regions of the [crate::blake2b::compression_gadget::Blake2bCompressionGadget], which
+/// validates its inputs and computes the witness before calling these.
+impl<F: PrimeField> Blake2bChip<F> {
+    /// Lays out a compression in its own region: the iv constants, the range-checked chaining
+    /// value and block, the counter and the finalization flags, followed by the rounds of
+    /// [Self::compress_state]. The values of every cell are taken from 'witness'.
+    pub(crate) fn compress_words(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        chaining_value: &[AssignedNative<F>; 8],
+        block: &[AssignedNative<F>; 16],
+        counter: &Blake2bCounter<F>,
+        [f0, f1]: [bool; 2],
+        witness: Value<&CompressionWitness>,
+    ) -> Result<[AssignedNative<F>; 8], Error> {
+        layouter.assign_region(
+            || "compression",
+            |mut region| {
+                let mut offset: usize = 0;
+                let iv_constants =
+                    self.assign_iv_constants_to_fixed_cells(&mut region, &mut offset)?;
+
+                let mut global_state: [AssignedBlake2bWord<F>; 8] = chaining_value
+                    .iter()
+                    .enumerate()
+                    .map(|(i, word)| {
+                        let value = witness.map(|w| w.chaining_value[i]);
+                        self.range_checked_word(&mut region, &mut offset, word, value)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()
+                    .unwrap();
+                let block: [AssignedBlake2bWord<F>; 16] = block
+                    .iter()
+                    .enumerate()
+                    .map(|(i, word)| {
+                        let value = witness.map(|w| w.block[i]);
+                        self.range_checked_word(&mut region, &mut offset, word, value)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .try_into()
+                    .unwrap();
+
+                let mut state: [AssignedBlake2bWord<F>; 16] = array::from_fn(|i| {
+                    if i < 8 { &global_state[i] } else { &iv_constants[i - 8] }.clone()
+                });
+                match counter {
+                    // As in [Self::compress], the xor of a constant counter with the iv is also a
+                    // constant.
+                    Blake2bCounter::Constant(counter) => {
+                        state[12] = AssignedBlake2bWord::assign_fixed_word(
+                            &mut region,
+                            "New state[12]",
+                            self.config.full_number_u64,
+                            offset,
+                            (*counter as u64 ^ IV_CONSTANTS[4]).into(),
+                        )?;
+                        state[13] = AssignedBlake2bWord::assign_fixed_word(
+                            &mut region,
+                            "New state[13]",
+                            self.config.limbs[0],
+                            offset,
+                            ((*counter >> 64) as u64 ^ IV_CONSTANTS[5]).into(),
+                        )?;
+                        offset += 1;
+                    }
+                    Blake2bCounter::Witness(counter) => {
+                        for (i, word) in counter.iter().enumerate() {
+                            let value = witness.map(|w| w.counter[i]);
+                            let counter_word =
+                                self.range_checked_word(&mut region, &mut offset, word, value)?;
+                            let new_value = value.map(|v| v ^ IV_CONSTANTS[4 + i].into());
+                            state[12 + i] = self
+                                .xor(
+                                    &state[12 + i],
+                                    &counter_word,
+                                    new_value,
+                                    &mut region,
+                                    &mut offset,
+                                )?
+                                .full_number;
+                        }
+                    }
+                }
+
+                if f0 {
+                    let negated_value = witness.map(|w| w.negated_state_14);
+                    state[14] = self.not(&state[14], negated_value, &mut region, &mut offset)?;
+                }
+                if f1 {
+                    let negated_value = witness.map(|w| w.negated_state_15);
+                    state[15] = self.not(&state[15], negated_value, &mut region, &mut offset)?;
+                }
+
+                self.compress_state(
+                    &mut region,
+                    &mut offset,
+                    &mut global_state,
+                    state,
+                    block,
+                    witness,
+                )?;
+                Ok(global_state.each_ref().map(AssignedBlake2bWord::to_native))
+            },
+        )
+    }
+
+    /// Lays out a mix in its own region: the range-checked words (a, b, c, d, x, y), whose values
+    /// are given in 'values', followed by the rows of [Blake2bInstructions::mix]. It returns the
+    /// new (a, b, c, d).
+    pub(crate) fn mix_words(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        words: &[AssignedNative<F>; 6],
+        values: Value<[Blake2bWord; 6]>,
+        witness: Value<MixWitness>,
+    ) -> Result<[AssignedNative<F>; 4], Error> {
+        layouter.assign_region(
+            || "mix",
+            |mut region| {
+                let mut offset: usize = 0;
+                let words = words
+                    .iter()
+                    .enumerate()
+                    .map(|(i, word)| {
+                        let value = values.map(|v| v[i]);
+                        self.range_checked_word(&mut region, &mut offset, word, value)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
+                // Only the first 4 words of the state take part in the mix.
+                let mut state: [AssignedBlake2bWord<F>; 16] =
+                    array::from_fn(|i| words[i % 4].clone());
+                self.mix(
+                    [0, 1, 2, 3],
+                    words[4].clone(),
+                    words[5].clone(),
+                    &mut state,
+                    &mut region,
+                    &mut offset,
+                    witness,
+                )?;
+                Ok(array::from_fn(|i| state[i].to_native()))
+            },
         )
     }
 }
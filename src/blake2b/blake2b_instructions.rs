@@ -1,8 +1,9 @@
+use crate::blake2b::witness::{Blake2bWitness, CompressionWitness, MixWitness};
 use crate::types::blake2b_word::AssignedBlake2bWord;
 use crate::types::byte::AssignedByte;
 use crate::types::AssignedNative;
 use ff::PrimeField;
-use midnight_proofs::circuit::{Layouter, Region};
+use midnight_proofs::circuit::{Layouter, Region, Value};
 use midnight_proofs::plonk::Error;
 
 pub(crate) type ConstantCells<F> =
@@ -38,6 +39,9 @@ pub trait Blake2bInstructions<F: PrimeField>: Clone {
     /// The global state corresponds to 8 cells containing 64-bit numbers, which are updated when
     /// some of those words change. A change in a state value is represented by changing the cell
     /// that represent that particular word in the state.
+    /// The values of every cell are taken from the natively precomputed 'witness'.
+    /// 'prefix_len' is the amount of bytes compressed into the global state before this call,
+    /// which is 0 unless the compressions of a prefix were precomputed outside the circuit.
     /// The return bytes of this function are the digest of the Blake2b computation.
     #[allow(clippy::too_many_arguments)]
     fn perform_blake2b_iterations(
@@ -46,14 +50,17 @@ pub trait Blake2bInstructions<F: PrimeField>: Clone {
         advice_offset: &mut usize,
         input: &[AssignedNative<F>],
         key: &[AssignedNative<F>],
+        prefix_len: usize,
         iv_constants: &[AssignedBlake2bWord<F>; 8],
         global_state: &mut [AssignedBlake2bWord<F>; 8],
         zero_constant_cell: AssignedNative<F>,
+        witness: &Value<Blake2bWitness>,
     ) -> Result<[AssignedByte<F>; 64], Error>;
 
     /// This method computes a compression round of Blake2b. The global state is update through
     /// consecutive calls of this method. If the algorithm is in its last round, the is_last_block
-    /// parameter should be set to true.
+    /// parameter should be set to true. The 'witness' holds the precomputed values of this
+    /// compression.
     #[allow(clippy::too_many_arguments)]
     fn compress(
         &self,
@@ -64,6 +71,7 @@ pub trait Blake2bInstructions<F: PrimeField>: Clone {
         current_block: [AssignedBlake2bWord<F>; 16],
         processed_bytes_count: u64,
         is_last_block: bool,
+        witness: Value<&CompressionWitness>,
     ) -> Result<[AssignedByte<F>; 64], Error>;
 
     /// This method computes a single round of mixing for the Blake2b algorithm.
@@ -72,6 +80,8 @@ pub trait Blake2bInstructions<F: PrimeField>: Clone {
     /// be processed in this mixing round.
     /// The 'state_indexes' are the indexes of the compress state that will take part on this
     /// mixing round. These are also needed to update the state at the end of the mixing.
+    /// The 'witness' holds the precomputed values of this mixing round.
+    #[allow(clippy::too_many_arguments)]
     fn mix(
         &self,
         state_indexes: [usize; 4],
@@ -80,5 +90,6 @@ pub trait Blake2bInstructions<F: PrimeField>: Clone {
         state: &mut [AssignedBlake2bWord<F>; 16],
         region: &mut Region<'_, F>,
         offset: &mut usize,
+        witness: Value<MixWitness>,
     ) -> Result<(), Error>;
 }
@@ -0,0 +1,114 @@
+//! The compression function of Blake2b and its G function (mix) as standalone gadgets, for
+//! constructions derived from Blake2b such as Argon2, BLAKE2X or custom sponges, which chain
+//! compressions in their own way instead of hashing a message.
+//!
+//! Words are given and returned as [AssignedNative] cells holding 64-bit values. Every word given
+//! to the gadget is range-checked, so the cells can come from any other gadget of the circuit.
+
+use crate::blake2b::blake2b_chip::{Blake2bChip, Blake2bConfig};
+use crate::blake2b::witness::{CompressionWitness, MixWitness};
+use crate::error::Blake2bError;
+use crate::types::blake2b_word::Blake2bWord;
+use crate::types::AssignedNative;
+use ff::PrimeField;
+use midnight_proofs::circuit::{Layouter, Value};
+
+/// The counter of bytes compressed so far, `t` in RFC 7693, which is 128 bits long.
+#[derive(Clone, Debug)]
+pub enum Blake2bCounter<F: PrimeField> {
+    /// A counter known at circuit building time, as in a hash of a message of fixed length.
+    Constant(u128),
+    /// A counter assigned in the circuit, as its low and high 64-bit words.
+    Witness([AssignedNative<F>; 2]),
+}
+
+/// A gadget that computes single compressions and mixes of Blake2b. It shares the configuration,
+/// and so the columns, gates and lookup tables, of the [Blake2bChip].
+#[derive(Clone, Debug)]
+pub struct Blake2bCompressionGadget<F: PrimeField> {
+    chip: Blake2bChip<F>,
+}
+
+impl<F: PrimeField> Blake2bCompressionGadget<F> {
+    /// The gadget over the configuration of a [Blake2bChip], see [Blake2bChip::configure].
+    pub fn new(config: &Blake2bConfig) -> Self {
+        Self {
+            chip: Blake2bChip::new(config),
+        }
+    }
+
+    /// Fills the lookup tables of the chip. It must be called once per circuit, unless the tables
+    /// are already filled by a [Blake2bChip] with the same configuration.
+    pub fn load(&self, layouter: &mut impl Layouter<F>) -> Result<(), Blake2bError> {
+        self.chip.load(layouter)
+    }
+
+    /// Compresses 'block' over 'chaining_value' with the given 'counter' and finalization flags:
+    /// 'f0' is set for the last block of a message and 'f1' for the last node of a tree hash.
+    /// It returns the new chaining value. Words are little-endian, as in RFC 7693, so the
+    /// chaining value after the last block is the digest read as 8 little-endian words.
+    /// It fails if any known value of the words or of a witness counter is not a 64-bit word.
+    pub fn compress(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        chaining_value: &[AssignedNative<F>; 8],
+        block: &[AssignedNative<F>; 16],
+        counter: &Blake2bCounter<F>,
+        f0: bool,
+        f1: bool,
+    ) -> Result<[AssignedNative<F>; 8], Blake2bError> {
+        let counter_value = match counter {
+            Blake2bCounter::Constant(counter) => Value::known(*counter),
+            Blake2bCounter::Witness(words) => {
+                words_from_cells(words)?.map(|[low, high]| (low.0 as u128) | (high.0 as u128) << 64)
+            }
+        };
+        let witness = words_from_cells(chaining_value)?
+            .zip(words_from_cells(block)?)
+            .zip(counter_value)
+            .map(|((chaining_value, block), counter)| {
+                CompressionWitness::generate_with_flags(
+                    &chaining_value.map(|word| word.0),
+                    &block.map(|word| word.0),
+                    counter,
+                    [f0, f1],
+                )
+            });
+        Ok(self.chip.compress_words(
+            layouter,
+            chaining_value,
+            block,
+            counter,
+            [f0, f1],
+            witness.as_ref(),
+        )?)
+    }
+
+    /// The G function of Blake2b over the state words 'v' = (a, b, c, d) and the message words
+    /// 'x' and 'y'. It returns the new (a, b, c, d). It fails if any known value of the words is
+    /// not a 64-bit word.
+    pub fn mix(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        v: &[AssignedNative<F>; 4],
+        x: &AssignedNative<F>,
+        y: &AssignedNative<F>,
+    ) -> Result<[AssignedNative<F>; 4], Blake2bError> {
+        let words = [v[0].clone(), v[1].clone(), v[2].clone(), v[3].clone(), x.clone(), y.clone()];
+        let values = words_from_cells(&words)?;
+        let witness =
+            values.map(|[a, b, c, d, x, y]| MixWitness::generate([a.0, b.0, c.0, d.0], x.0, y.0));
+        Ok(self.chip.mix_words(layouter, &words, values, witness)?)
+    }
+}
+
+/// Reads the 64-bit words held by a list of cells. The result is unknown if any of the cell values
+/// is unknown, and it fails if any of the known values is not a 64-bit word.
+fn words_from_cells<F: PrimeField, const N: usize>(
+    cells: &[AssignedNative<F>; N],
+) -> Result<Value<[Blake2bWord; N]>, Blake2bError> {
+    let words: Value<Option<Vec<Blake2bWord>>> =
+        cells.iter().map(|cell| cell.value().map(|v| Blake2bWord::try_from_field(*v))).collect();
+    words.error_if_known_and(|words| words.is_none()).map_err(|_| Blake2bError::NonWordInput)?;
+    Ok(words.map(|words| words.unwrap().try_into().unwrap()))
+}
@@ -1,6 +1,6 @@
 //! A chip defining a Blake2b hash invocation. This interface works with in/out consisting of
-//! AssignedNative. The algorithm expects its values to be in the range of a Byte, and will fail if
-//! they're not.
+//! AssignedNative. The algorithm expects its values to be in the range of a Byte, and returns a
+//! [crate::error::Blake2bError::NonByteInput] error if they're not.
 //!
 //! The chip relies on a set of basic instructions, implemented as a trait called
 //! [Blake2bInstructions]. There is currently one implementation of the instruction set:
@@ -15,8 +15,21 @@ pub(crate) mod blake2b_instructions;
 /// Basic definitions and constants for the blake2b chip.
 pub(crate) mod utils;
 
+/// Native computation of all the values assigned by the chip. With the `parallel` feature,
+/// compressions are computed in parallel.
+pub(crate) mod witness;
+
+/// Labels of the rows of the chip, recorded while they are assigned.
+pub(crate) mod row_labels;
+
 /// These are the separated optimizations.
 pub mod blake2b_chip;
 
+/// The compression function and the G function of Blake2b as standalone gadgets.
+pub mod compression_gadget;
+
 /// Number of advice columns required by the chip.
 pub const NB_BLAKE2B_ADVICE_COLS: usize = 9;
+
+/// Number of rows of the lookup tables of the chip, i.e. the ones of the 16-bit xor table.
+pub const NB_BLAKE2B_TABLE_ROWS: usize = 1 << 16;
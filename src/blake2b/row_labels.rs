@@ -0,0 +1,82 @@
+use std::sync::{Arc, Mutex};
+
+/// What a row of the region of the chip computes: the block, round and mix it belongs to, when
+/// it belongs to one, and the operation.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RowLabel {
+    /// The block being compressed. The key, if any, is block 0.
+    pub(crate) block: Option<usize>,
+    /// The round of the compression, for the rows of a mix.
+    pub(crate) round: Option<usize>,
+    /// The index of the mix in its round, for the rows of a mix.
+    pub(crate) mix: Option<usize>,
+    /// The operation, e.g. "b = b ^ c".
+    pub(crate) operation: String,
+}
+
+/// The labels of the last labelled region the chip assigned.
+#[derive(Debug, Default)]
+struct RecordedLabels {
+    region: String,
+    /// The block, round and mix of the operations being assigned.
+    context: RowLabel,
+    /// The offset of the first row of every operation, with its label, in assignment order.
+    operations: Vec<(usize, RowLabel)>,
+}
+
+/// Labels of the rows of the region of the chip, recorded by the chip itself while it assigns
+/// them. A row belongs to the last operation that started at or before its offset, so these
+/// labels follow any change in the order the chip assigns its rows.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RowLabels(Arc<Mutex<RecordedLabels>>);
+
+impl RowLabels {
+    /// Starts the labels of the region 'name'. Floor planners may assign a region more than once,
+    /// e.g. to measure it first, so only the labels of its last assignment are kept.
+    pub(crate) fn enter_region(&self, name: &str) {
+        *self.0.lock().unwrap() = RecordedLabels {
+            region: name.to_string(),
+            ..RecordedLabels::default()
+        };
+    }
+
+    /// Sets the block of the next operations, which are outside any mix.
+    pub(crate) fn set_block(&self, block: Option<usize>) {
+        self.0.lock().unwrap().context = RowLabel {
+            block,
+            ..RowLabel::default()
+        };
+    }
+
+    /// Sets the (round, mix) of the next operations, or none when they are outside a mix.
+    pub(crate) fn set_mix(&self, round_and_mix: Option<(usize, usize)>) {
+        let context = &mut self.0.lock().unwrap().context;
+        context.round = round_and_mix.map(|(round, _)| round);
+        context.mix = round_and_mix.map(|(_, mix)| mix);
+    }
+
+    /// Records that the rows from 'offset' on belong to 'operation'.
+    pub(crate) fn start_operation(&self, offset: usize, operation: impl Into<String>) {
+        let mut recorded = self.0.lock().unwrap();
+        let label = RowLabel {
+            operation: operation.into(),
+            ..recorded.context.clone()
+        };
+        recorded.operations.push((offset, label));
+    }
+
+    /// The name of the labelled region, if any.
+    pub(crate) fn region(&self) -> Option<String> {
+        let recorded = self.0.lock().unwrap();
+        (!recorded.region.is_empty()).then(|| recorded.region.clone())
+    }
+
+    /// The label of the row at 'offset' of the labelled region, if some operation starts at or
+    /// before it.
+    pub(crate) fn label_at(&self, offset: usize) -> Option<RowLabel> {
+        let recorded = self.0.lock().unwrap();
+        let operations = &recorded.operations;
+        let index = operations.partition_point(|(start, _)| *start <= offset);
+        index.checked_sub(1).map(|index| operations[index].1.clone())
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::Blake2bError;
 use crate::types::AssignedNative;
 use ff::PrimeField;
 use midnight_proofs::circuit::Region;
@@ -8,10 +9,14 @@ use crate::types::row::AssignedRow;
 /// Enforces the output and key sizes.
 /// Output size must be between 1 and 64 bytes.
 /// Key size must be between 0 and 64 bytes.
-pub(crate) fn enforce_input_sizes(output_size: usize, key_size: usize) {
-    assert!(output_size <= 64, "Output size must be between 1 and 64 bytes");
-    assert!(output_size > 0, "Output size must be between 1 and 64 bytes");
-    assert!(key_size <= 64, "Key size must be between 1 and 64 bytes");
+pub(crate) fn enforce_input_sizes(output_size: usize, key_size: usize) -> Result<(), Blake2bError> {
+    if output_size == 0 || output_size > 64 {
+        return Err(Blake2bError::InvalidOutputSize(output_size));
+    }
+    if key_size > 64 {
+        return Err(Blake2bError::InvalidKeySize(key_size));
+    }
+    Ok(())
 }
 
 /// Extracts the full number cell of each of the state rows
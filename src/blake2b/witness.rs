@@ -0,0 +1,375 @@
+use crate::error::Blake2bError;
+use crate::blake2b::utils::{
+    compute_processed_bytes_count_value_for_iteration, get_total_blocks_count, ABCD,
+    BLAKE2B_BLOCK_SIZE, IV_CONSTANTS, SIGMA,
+};
+use crate::types::blake2b_word::Blake2bWord;
+use crate::types::byte::Byte;
+use crate::types::AssignedNative;
+use ff::PrimeField;
+use midnight_proofs::circuit::Value;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Native values produced by one mix (G function) invocation. Every field holds the two values
+/// that the corresponding variable takes during the mix, in the order in which the chip assigns
+/// them in the trace. The limbs of each word are its little endian bytes, and the carry of each
+/// addition is stored next to its result.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct MixWitness {
+    pub(crate) a_plus_b: [Blake2bWord; 2],
+    pub(crate) a_plus_b_carry: [bool; 2],
+    pub(crate) a: [Blake2bWord; 2],
+    pub(crate) a_carry: [bool; 2],
+    pub(crate) d_xor_a: [Blake2bWord; 2],
+    pub(crate) d: [Blake2bWord; 2],
+    pub(crate) c: [Blake2bWord; 2],
+    pub(crate) c_carry: [bool; 2],
+    pub(crate) b_xor_c: [Blake2bWord; 2],
+    pub(crate) b: [Blake2bWord; 2],
+}
+
+/// Native values of a whole compression. The 96 mixes are stored in the order in which the
+/// compress function calls them (12 rounds of 8 mixes).
+#[derive(Clone, Debug)]
+pub(crate) struct CompressionWitness {
+    /// The chaining value the block is compressed over.
+    pub(crate) chaining_value: [Blake2bWord; 8],
+    /// The 16 words of the (padded) block that is compressed.
+    pub(crate) block: [Blake2bWord; 16],
+    /// The low and high words of the counter of processed bytes.
+    pub(crate) counter: [Blake2bWord; 2],
+    /// The negation of the state[14] word, only used in the last block.
+    pub(crate) negated_state_14: Blake2bWord,
+    /// The negation of the state[15] word, only used in the last node of a tree hash.
+    pub(crate) negated_state_15: Blake2bWord,
+    pub(crate) mixes: Vec<MixWitness>,
+    /// The values of global_state[i] ^ state[i] for each i in 0..8.
+    pub(crate) state_xor: [Blake2bWord; 8],
+    /// The new global state: global_state[i] ^ state[i] ^ state[i + 8] for each i in 0..8.
+    pub(crate) new_state: [Blake2bWord; 8],
+}
+
+/// Every intermediate value of a Blake2b hash, computed natively before any cell is assigned.
+/// The chip then just copies these values into the trace.
+///
+/// Compressions depend on each other only through their chaining value, so the chaining values
+/// are computed first and then, with the `parallel` feature, the full trace of each compression
+/// is computed in a separate thread.
+#[derive(Clone, Debug)]
+pub(crate) struct Blake2bWitness {
+    pub(crate) compressions: Vec<CompressionWitness>,
+}
+
+impl Blake2bWitness {
+    /// Builds the witness from the assigned input and key cells. The result is unknown if any
+    /// of the cell values is unknown (for example, during key generation), and it fails if any
+    /// of the known values is not a byte.
+    pub(crate) fn from_assigned_bytes<F: PrimeField>(
+        input: &[AssignedNative<F>],
+        key: &[AssignedNative<F>],
+        output_size: usize,
+    ) -> Result<Value<Self>, Blake2bError> {
+        let input_bytes = Self::bytes_from_cells(input)?;
+        let key_bytes = Self::bytes_from_cells(key)?;
+        Ok(input_bytes.zip(key_bytes).map(|(input, key)| Self::generate(&input, &key, output_size)))
+    }
+
+    /// Builds the witness of the hash of a message whose first 'prefix_len' bytes were already
+    /// compressed into 'prefix_state', from the assigned cells of the rest of the message.
+    pub(crate) fn from_assigned_suffix<F: PrimeField>(
+        prefix_state: [u64; 8],
+        prefix_len: usize,
+        suffix: &[AssignedNative<F>],
+    ) -> Result<Value<Self>, Blake2bError> {
+        let suffix_bytes = Self::bytes_from_cells(suffix)?;
+        Ok(suffix_bytes
+            .map(|suffix| Self::generate_from_state(prefix_state, prefix_len, &suffix, &[])))
+    }
+
+    /// Computes the witness of the hash of 'input' with 'key' and an output of 'output_size'
+    /// bytes.
+    pub(crate) fn generate(input: &[u8], key: &[u8], output_size: usize) -> Self {
+        Self::generate_from_state(Self::initial_state(key.len(), output_size), 0, input, key)
+    }
+
+    /// Computes the witness of the compressions of 'input' and 'key' starting from the chaining
+    /// value 'state', with 'prefix_len' bytes compressed before them.
+    fn generate_from_state(state: [u64; 8], prefix_len: usize, input: &[u8], key: &[u8]) -> Self {
+        let blocks = Self::blocks_for(input, key);
+        let total_blocks = blocks.len();
+        let counters = (0..total_blocks).map(|i| {
+            prefix_len as u64
+                + compute_processed_bytes_count_value_for_iteration(
+                    i,
+                    i == total_blocks - 1,
+                    input.len(),
+                    key.is_empty(),
+                )
+        });
+        let jobs: Vec<([u64; 16], u64, bool)> = blocks
+            .into_iter()
+            .zip(counters)
+            .enumerate()
+            .map(|(i, (block, counter))| (block, counter, i == total_blocks - 1))
+            .collect();
+
+        Self {
+            compressions: Self::compressions_for(state, jobs),
+        }
+    }
+
+    /// Natively computes the chaining value after compressing 'key' and 'prefix', which must be
+    /// made of full blocks, for a hash with an output of 'output_size' bytes. None of these blocks
+    /// is the last one of the hash.
+    pub(crate) fn prefix_state(prefix: &[u8], key: &[u8], output_size: usize) -> [u64; 8] {
+        let mut blocks: Vec<&[u8]> = prefix.chunks(BLAKE2B_BLOCK_SIZE).collect();
+        if !key.is_empty() {
+            blocks.insert(0, key);
+        }
+        let mut state = Self::initial_state(key.len(), output_size);
+        for (i, block) in blocks.into_iter().enumerate() {
+            let mut padded_block = block.to_vec();
+            padded_block.resize(BLAKE2B_BLOCK_SIZE, 0);
+            let words: [u64; 16] = std::array::from_fn(|j| {
+                u64::from_le_bytes(padded_block[j * 8..(j + 1) * 8].try_into().unwrap())
+            });
+            let counter = (BLAKE2B_BLOCK_SIZE * (i + 1)) as u64;
+            state =
+                CompressionWitness::generate(&state, &words, counter, false).new_state.map(|w| w.0);
+        }
+        state
+    }
+
+    /// The initial state depends only on the key and output sizes.
+    fn initial_state(key_size: usize, output_size: usize) -> [u64; 8] {
+        let mut state = IV_CONSTANTS;
+        state[0] ^= 0x01010000u64 ^ ((key_size as u64) << 8) ^ output_size as u64;
+        state
+    }
+
+    /// Without the `parallel` feature, compressions are computed one after the other.
+    #[cfg(not(feature = "parallel"))]
+    fn compressions_for(
+        initial_state: [u64; 8],
+        jobs: Vec<([u64; 16], u64, bool)>,
+    ) -> Vec<CompressionWitness> {
+        let mut state = initial_state;
+        jobs.into_iter()
+            .map(|(block, counter, is_last_block)| {
+                let compression =
+                    CompressionWitness::generate(&state, &block, counter, is_last_block);
+                state = compression.new_state.map(|w| w.0);
+                compression
+            })
+            .collect()
+    }
+
+    /// With the `parallel` feature, the chaining values are computed in a first sequential pass
+    /// and then every compression is recomputed in parallel, keeping all its intermediate values.
+    #[cfg(feature = "parallel")]
+    fn compressions_for(
+        initial_state: [u64; 8],
+        jobs: Vec<([u64; 16], u64, bool)>,
+    ) -> Vec<CompressionWitness> {
+        let mut chaining_values = Vec::with_capacity(jobs.len());
+        let mut state = initial_state;
+        for (block, counter, is_last_block) in jobs.iter() {
+            chaining_values.push(state);
+            state = compress_native(&state, block, *counter, *is_last_block);
+        }
+        jobs.into_par_iter()
+            .zip(chaining_values.into_par_iter())
+            .map(|((block, counter, is_last_block), state)| {
+                CompressionWitness::generate(&state, &block, counter, is_last_block)
+            })
+            .collect()
+    }
+
+    /// Splits the key and the input in the blocks that are compressed, in little endian words.
+    /// This follows the same padding rules as the chip.
+    fn blocks_for(input: &[u8], key: &[u8]) -> Vec<[u64; 16]> {
+        let mut padded_blocks: Vec<Vec<u8>> = Vec::new();
+        if !key.is_empty() {
+            padded_blocks.push(key.to_vec());
+        }
+        padded_blocks.extend(input.chunks(BLAKE2B_BLOCK_SIZE).map(|chunk| chunk.to_vec()));
+
+        let total_blocks = get_total_blocks_count(
+            input.len().div_ceil(BLAKE2B_BLOCK_SIZE),
+            input.is_empty(),
+            key.is_empty(),
+        );
+        padded_blocks.resize(total_blocks, vec![]);
+
+        padded_blocks
+            .into_iter()
+            .map(|mut block| {
+                block.resize(BLAKE2B_BLOCK_SIZE, 0);
+                std::array::from_fn(|i| {
+                    u64::from_le_bytes(block[i * 8..(i + 1) * 8].try_into().unwrap())
+                })
+            })
+            .collect()
+    }
+
+    /// Reads the byte values of a list of cells. This is the only place where field elements are
+    /// converted into integers, everything else is computed natively from these bytes.
+    fn bytes_from_cells<F: PrimeField>(
+        cells: &[AssignedNative<F>],
+    ) -> Result<Value<Vec<u8>>, Blake2bError> {
+        let bytes: Value<Option<Vec<u8>>> = cells
+            .iter()
+            .map(|cell| cell.value().map(|v| Byte::try_from_field(*v).map(|byte| byte.0)))
+            .collect();
+        bytes
+            .error_if_known_and(|bytes| bytes.is_none())
+            .map_err(|_| Blake2bError::NonByteInput)?;
+        Ok(bytes.map(Option::unwrap_or_default))
+    }
+}
+
+impl CompressionWitness {
+    /// Computes the compression of 'block' over the chaining value 'global_state', keeping all
+    /// the intermediate values.
+    fn generate(
+        global_state: &[u64; 8],
+        block: &[u64; 16],
+        processed_bytes_count: u64,
+        is_last_block: bool,
+    ) -> Self {
+        Self::generate_with_flags(
+            global_state,
+            block,
+            processed_bytes_count as u128,
+            [is_last_block, false],
+        )
+    }
+
+    /// Computes the compression of 'block' over the chaining value 'global_state' with the 128-bit
+    /// 'counter' and the finalization flags f0 (last block) and f1 (last node), keeping all the
+    /// intermediate values.
+    pub(crate) fn generate_with_flags(
+        global_state: &[u64; 8],
+        block: &[u64; 16],
+        counter: u128,
+        [f0, f1]: [bool; 2],
+    ) -> Self {
+        let mut state = initial_compression_state(global_state, counter);
+        let negated_state_14 = Blake2bWord(!state[14]);
+        let negated_state_15 = Blake2bWord(!state[15]);
+        if f0 {
+            state[14] = negated_state_14.0;
+        }
+        if f1 {
+            state[15] = negated_state_15.0;
+        }
+
+        let mut mixes = Vec::with_capacity(12 * 8);
+        for sigma in SIGMA.iter() {
+            for (j, indexes) in ABCD.iter().enumerate() {
+                mixes.push(mix(*indexes, block[sigma[2 * j]], block[sigma[2 * j + 1]], &mut state));
+            }
+        }
+
+        let state_xor: [Blake2bWord; 8] =
+            std::array::from_fn(|i| (global_state[i] ^ state[i]).into());
+        let new_state: [Blake2bWord; 8] =
+            std::array::from_fn(|i| state_xor[i] ^ state[i + 8].into());
+
+        Self {
+            chaining_value: global_state.map(Blake2bWord),
+            block: block.map(Blake2bWord),
+            counter: [Blake2bWord(counter as u64), Blake2bWord((counter >> 64) as u64)],
+            negated_state_14,
+            negated_state_15,
+            mixes,
+            state_xor,
+            new_state,
+        }
+    }
+}
+
+/// The 16-word state at the beginning of a compression, before the finalization flags are
+/// applied.
+fn initial_compression_state(global_state: &[u64; 8], counter: u128) -> [u64; 16] {
+    let mut state = [0u64; 16];
+    state[..8].copy_from_slice(global_state);
+    state[8..].copy_from_slice(&IV_CONSTANTS);
+    state[12] ^= counter as u64;
+    state[13] ^= (counter >> 64) as u64;
+    state
+}
+
+/// Computes a compression without keeping the intermediate values. It's only used to obtain
+/// the chaining values before computing the compressions in parallel.
+#[cfg(feature = "parallel")]
+fn compress_native(
+    global_state: &[u64; 8],
+    block: &[u64; 16],
+    processed_bytes_count: u64,
+    is_last_block: bool,
+) -> [u64; 8] {
+    let mut state = initial_compression_state(global_state, processed_bytes_count as u128);
+    if is_last_block {
+        state[14] = !state[14];
+    }
+    for sigma in SIGMA.iter() {
+        for (j, indexes) in ABCD.iter().enumerate() {
+            mix(*indexes, block[sigma[2 * j]], block[sigma[2 * j + 1]], &mut state);
+        }
+    }
+    std::array::from_fn(|i| global_state[i] ^ state[i] ^ state[i + 8])
+}
+
+impl MixWitness {
+    /// Computes the G function over the words 'v' = (a, b, c, d) of the state and the message
+    /// words 'x' and 'y', keeping all the intermediate values. The new (a, b, c, d) are the last
+    /// value of each word in the witness.
+    pub(crate) fn generate(v: [u64; 4], x: u64, y: u64) -> Self {
+        let mut state = [0u64; 16];
+        state[..4].copy_from_slice(&v);
+        mix([0, 1, 2, 3], x, y, &mut state)
+    }
+}
+
+/// The G function of Blake2b. It updates the state and returns the values of all the
+/// intermediate steps.
+fn mix(state_indexes: [usize; 4], x: u64, y: u64, state: &mut [u64; 16]) -> MixWitness {
+    let [ia, ib, ic, id] = state_indexes;
+    let (v_a, v_b, v_c, v_d) = (state[ia], state[ib], state[ic], state[id]);
+
+    let (a_plus_b_0, a_plus_b_carry_0) = v_a.overflowing_add(v_b);
+    let (a_0, a_carry_0) = a_plus_b_0.overflowing_add(x);
+    let d_xor_a_0 = v_d ^ a_0;
+    let d_0 = d_xor_a_0.rotate_right(32);
+    let (c_0, c_carry_0) = d_0.overflowing_add(v_c);
+    let b_xor_c_0 = v_b ^ c_0;
+    let b_0 = b_xor_c_0.rotate_right(24);
+
+    let (a_plus_b_1, a_plus_b_carry_1) = b_0.overflowing_add(a_0);
+    let (a_1, a_carry_1) = a_plus_b_1.overflowing_add(y);
+    let d_xor_a_1 = d_0 ^ a_1;
+    let d_1 = d_xor_a_1.rotate_right(16);
+    let (c_1, c_carry_1) = d_1.overflowing_add(c_0);
+    let b_xor_c_1 = b_0 ^ c_1;
+    let b_1 = b_xor_c_1.rotate_right(63);
+
+    state[ia] = a_1;
+    state[ib] = b_1;
+    state[ic] = c_1;
+    state[id] = d_1;
+
+    MixWitness {
+        a_plus_b: [a_plus_b_0.into(), a_plus_b_1.into()],
+        a_plus_b_carry: [a_plus_b_carry_0, a_plus_b_carry_1],
+        a: [a_0.into(), a_1.into()],
+        a_carry: [a_carry_0, a_carry_1],
+        d_xor_a: [d_xor_a_0.into(), d_xor_a_1.into()],
+        d: [d_0.into(), d_1.into()],
+        c: [c_0.into(), c_1.into()],
+        c_carry: [c_carry_0, c_carry_1],
+        b_xor_c: [b_xor_c_0.into(), b_xor_c_1.into()],
+        b: [b_0.into(), b_1.into()],
+    }
+}
@@ -0,0 +1,120 @@
+//! Errors returned by the public entry points of the crate.
+
+use midnight_proofs::plonk::Error;
+use std::ops::Range;
+use std::{fmt, io};
+
+/// The errors that can occur when configuring, synthesizing, proving or verifying a Blake2b hash.
+#[derive(Debug)]
+pub enum Blake2bError {
+    /// The output size must be between 1 and 64 bytes.
+    InvalidOutputSize(usize),
+    /// The key size must be between 0 and 64 bytes.
+    InvalidKeySize(usize),
+    /// An input or key cell holds a value that is not in the range [0, 255].
+    NonByteInput,
+    /// A cell of a chaining value, a message block or a counter holds a value that is not in the
+    /// range [0, 2^64 - 1].
+    NonWordInput,
+    /// A range of public input bytes is not within the input.
+    InvalidPublicInputRange(Range<usize>),
+    /// A prefix compressed outside the circuit must be made of full blocks.
+    InvalidPrefixLength(usize),
+    /// The message after a precomputed prefix must not be empty.
+    EmptySuffix,
+    /// The circuits proven together must have the same shape as the first one, see
+    /// [crate::usage_utils::blake2b_circuit::Blake2bCircuit::has_same_shape].
+    MismatchedCircuitShape(usize),
+    /// The parameters of the commitment scheme support circuits of up to 2^k rows, with the first
+    /// k, and the circuit needs 2^k rows, with the second k.
+    ParamsTooSmall(u32, u32),
+    /// A hex string of an input, key or digest is not valid hex.
+    InvalidHex(hex::FromHexError),
+    /// The Mock Prover found the circuit unsatisfied, with a message for every failure.
+    Unsatisfied(Vec<String>),
+    /// An error of the proof system while synthesizing, proving or verifying the circuit.
+    Synthesis(Error),
+    /// An error while reading or writing params, keys, proofs or reports.
+    Io(io::Error),
+}
+
+impl fmt::Display for Blake2bError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Blake2bError::InvalidOutputSize(size) => {
+                write!(f, "Output size must be between 1 and 64 bytes, got {size}")
+            }
+            Blake2bError::InvalidKeySize(size) => {
+                write!(f, "Key size must be between 0 and 64 bytes, got {size}")
+            }
+            Blake2bError::NonByteInput => write!(f, "Input and key values must be bytes"),
+            Blake2bError::NonWordInput => {
+                write!(f, "Chaining value, block and counter values must be 64-bit words")
+            }
+            Blake2bError::InvalidPublicInputRange(range) => {
+                write!(f, "Public input range {range:?} is not within the input")
+            }
+            Blake2bError::InvalidPrefixLength(length) => {
+                write!(f, "Precomputed prefix length must be a multiple of 128 bytes, got {length}")
+            }
+            Blake2bError::EmptySuffix => {
+                write!(f, "The message after a precomputed prefix must not be empty")
+            }
+            Blake2bError::MismatchedCircuitShape(index) => {
+                write!(f, "Circuit {index} doesn't have the shape of the first circuit")
+            }
+            Blake2bError::ParamsTooSmall(params_k, circuit_k) => {
+                write!(
+                    f,
+                    "The params are for k = {params_k}, but the circuit needs k = {circuit_k}"
+                )
+            }
+            Blake2bError::InvalidHex(error) => write!(f, "Invalid hex string: {error}"),
+            Blake2bError::Unsatisfied(failures) => {
+                write!(f, "The Mock Prover failed:\n{}", failures.join("\n"))
+            }
+            Blake2bError::Synthesis(error) => write!(f, "Synthesis error: {error}"),
+            Blake2bError::Io(error) => write!(f, "I/O error: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for Blake2bError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Blake2bError::InvalidHex(error) => Some(error),
+            Blake2bError::Synthesis(error) => Some(error),
+            Blake2bError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for Blake2bError {
+    fn from(error: Error) -> Self {
+        Blake2bError::Synthesis(error)
+    }
+}
+
+impl From<hex::FromHexError> for Blake2bError {
+    fn from(error: hex::FromHexError) -> Self {
+        Blake2bError::InvalidHex(error)
+    }
+}
+
+impl From<io::Error> for Blake2bError {
+    fn from(error: io::Error) -> Self {
+        Blake2bError::Io(error)
+    }
+}
+
+/// Allows the chip to be used inside [midnight_proofs::plonk::Circuit::synthesize], which must
+/// return the error type of the proof system.
+impl From<Blake2bError> for Error {
+    fn from(error: Blake2bError) -> Self {
+        match error {
+            Blake2bError::Synthesis(error) => error,
+            error => Error::Synthesis(error.to_string()),
+        }
+    }
+}
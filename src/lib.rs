@@ -15,5 +15,6 @@ pub(crate) mod base_operations;
 #[cfg(test)]
 mod tests;
 pub mod blake2b;
+pub mod error;
 pub mod types;
 pub mod usage_utils;
@@ -2,9 +2,10 @@ use super::*;
 use midnight_curves::bls12_381::Fq;
 use ff::Field;
 use std::marker::PhantomData;
+use crate::base_operations::xor::populate_xor_lookup_table;
 use crate::base_operations::{
     create_limb_decomposition_gate, create_range_check_gate, generate_row_from_word_and_keep_row,
-    populate_lookup_table,
+    populate_lookup_table, RangeTable,
 };
 use crate::types::AssignedNative;
 use crate::types::blake2b_word::Blake2bWord;
@@ -68,8 +69,8 @@ struct Decompose8Config {
     /// Selector that turns on the gate that defines if the limbs should be range-checked
     q_range: Selector,
 
-    /// Table of [0, 2^8) to check if the limb is in the correct range
-    t_range: TableColumn,
+    /// Table used to check if the limb is in the range [0, 2^8)
+    range_table: RangeTable,
 }
 
 impl Decompose8Config {
@@ -81,31 +82,45 @@ impl Decompose8Config {
         full_number_u64: Column<Advice>,
         limbs: [Column<Advice>; 8],
     ) -> Self {
-        let q_range = meta.complex_selector();
         let t_range = meta.lookup_table_column();
+        Self::configure_with_range_table(meta, full_number_u64, limbs, RangeTable::Range(t_range))
+    }
+
+    /// Same as [configure], but the limbs are range-checked against the given table.
+    fn configure_with_range_table<F: PrimeField>(
+        meta: &mut ConstraintSystem<F>,
+        full_number_u64: Column<Advice>,
+        limbs: [Column<Advice>; 8],
+        range_table: RangeTable,
+    ) -> Self {
+        let q_range = meta.complex_selector();
         let q_decompose = meta.complex_selector();
 
         // Gate that checks if the decomposition is correct
         create_limb_decomposition_gate(meta, q_decompose, full_number_u64, limbs);
 
         // Range checks for all the limbs (range [0,255])
-        create_range_check_gate(meta, t_range, q_range, limbs);
+        create_range_check_gate(meta, range_table, q_range, limbs);
 
         Self {
             full_number_u64,
             limbs,
             q_decompose,
-            t_range,
+            range_table,
             q_range,
         }
     }
 
-    /// Fills the [t_range] table with values in the range [0,255]
+    /// Fills the range table, which holds the values in the range [0,255] (or all the 8-bit xor
+    /// operations when the xor table is used)
     fn populate_lookup_table<F: PrimeField>(
         &self,
         layouter: &mut impl Layouter<F>,
     ) -> Result<(), Error> {
-        populate_lookup_table(layouter, self.t_range)
+        match self.range_table {
+            RangeTable::Range(t_range) => populate_lookup_table(layouter, t_range),
+            RangeTable::Xor(xor_table) => populate_xor_lookup_table(layouter, xor_table),
+        }
     }
     /// Given an explicit vector of values, it assigns the full number and the limbs in a row of the trace
     /// row size is T + 1
@@ -0,0 +1,144 @@
+use super::*;
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, Blake2bInstanceMode};
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use midnight_curves::bls12_381::Bls12;
+use midnight_proofs::plonk::{Error, VerifyingKey};
+use midnight_proofs::poly::kzg::{params::ParamsKZG, KZGCommitmentScheme};
+
+/// Messages of the same length, so that their circuits share the keys.
+const MESSAGES: [&[u8]; 2] = [b"first message", b"other message"];
+
+/// The digest of a message along with its proof.
+type ProvenMessage = (Vec<Fq>, Vec<u8>);
+
+/// The keys of the circuits of [MESSAGES], and the digest and proof of every message.
+fn proven_messages(
+) -> (ParamsKZG<Bls12>, VerifyingKey<Fq, KZGCommitmentScheme<Bls12>>, Vec<ProvenMessage>) {
+    let circuit_for = |message: &[u8]| {
+        let input_values =
+            message.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect();
+        Bls12CircuitRunner::create_circuit_for_inputs(input_values, message.len(), vec![], 0, 64)
+            .unwrap()
+    };
+    let circuit = circuit_for(MESSAGES[0]);
+    let params = Bls12CircuitRunner::create_params(circuit.k());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+
+    let proven = MESSAGES
+        .iter()
+        .map(|message| {
+            let digest = expected_output_for(message, 64);
+            let proof =
+                Bls12CircuitRunner::create_proof(&digest, circuit_for(message), &params, &pk)
+                    .unwrap()
+                    .proof;
+            (digest, proof)
+        })
+        .collect();
+    (params, vk, proven)
+}
+
+#[test]
+fn test_batch_of_valid_proofs_verifies() {
+    let (params, vk, proven) = proven_messages();
+    let batch: Vec<(&[Fq], &[u8])> =
+        proven.iter().map(|(digest, proof)| (digest.as_slice(), proof.as_slice())).collect();
+
+    Bls12CircuitRunner::verify_batch(&vk, &params, &batch).unwrap();
+    Bls12CircuitRunner::verify_batch(&vk, &params, &batch[..1]).unwrap();
+    Bls12CircuitRunner::verify_batch(&vk, &params, &[]).unwrap();
+}
+
+#[test]
+fn test_batch_with_one_invalid_proof_is_rejected() {
+    let (params, vk, proven) = proven_messages();
+    let mut batch: Vec<(&[Fq], &[u8])> =
+        proven.iter().map(|(digest, proof)| (digest.as_slice(), proof.as_slice())).collect();
+
+    // Every proof is valid on its own, but the second one is checked against the digest of the
+    // first message.
+    batch[1].0 = &proven[0].0;
+    assert!(Bls12CircuitRunner::verify_with_vk(&vk, &params, &proven[1].0, &proven[1].1).is_ok());
+    assert!(Bls12CircuitRunner::verify_batch(&vk, &params, &batch).is_err());
+
+    // A corrupted proof fails when it is prepared or when the batch is checked.
+    let mut corrupted_proof = proven[1].1.clone();
+    let middle = corrupted_proof.len() / 2;
+    corrupted_proof[middle] ^= 1;
+    batch[1] = (&proven[1].0, &corrupted_proof);
+    assert!(Bls12CircuitRunner::verify_batch(&vk, &params, &batch).is_err());
+}
+
+#[test]
+fn test_batch_of_proofs_with_committed_digests() {
+    let circuit_for = |message: &[u8]| {
+        Blake2bCircuit::new_with_instance_mode(
+            message.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect(),
+            message.len(),
+            vec![],
+            0,
+            64,
+            Blake2bInstanceMode::CommittedDigest,
+        )
+        .unwrap()
+    };
+    let circuit = circuit_for(MESSAGES[0]);
+    let params = Bls12CircuitRunner::create_params(circuit.k());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+
+    let proofs: Vec<Vec<u8>> = MESSAGES
+        .iter()
+        .map(|message| {
+            let digest = expected_output_for(message, 64);
+            Bls12CircuitRunner::create_proof_with_instances(
+                &[&digest],
+                circuit_for(message),
+                &params,
+                &pk,
+            )
+            .unwrap()
+            .proof
+        })
+        .collect();
+    let proofs: Vec<&[u8]> = proofs.iter().map(|proof| proof.as_slice()).collect();
+    let commitments: Vec<_> = MESSAGES
+        .iter()
+        .map(|message| {
+            Bls12CircuitRunner::commit_to_instance(&vk, &params, &expected_output_for(message, 64))
+        })
+        .collect();
+    let mut committed_instances: Vec<&[_]> = commitments.iter().map(std::slice::from_ref).collect();
+    let instances: Vec<&[&[Fq]]> = vec![&[]; proofs.len()];
+
+    Bls12CircuitRunner::verify_batch_with_committed_instances(
+        &vk,
+        &params,
+        &committed_instances,
+        &instances,
+        &proofs,
+    )
+    .unwrap();
+
+    // The second proof is checked against the commitment to the digest of the first message.
+    committed_instances[1] = committed_instances[0];
+    let result = Bls12CircuitRunner::verify_batch_with_committed_instances(
+        &vk,
+        &params,
+        &committed_instances,
+        &instances,
+        &proofs,
+    );
+    assert!(matches!(result, Err(Blake2bError::Synthesis(Error::Opening))));
+
+    let result = Bls12CircuitRunner::verify_batch_with_committed_instances(
+        &vk,
+        &params,
+        &committed_instances[..1],
+        &instances,
+        &proofs,
+    );
+    assert!(matches!(result, Err(Blake2bError::Synthesis(Error::InvalidInstances))));
+}
@@ -0,0 +1,157 @@
+//! A circuit that hashes its input with the chip configured in any of the ways a circuit can
+//! configure it, shared by the tests of those configurations.
+
+use super::*;
+use crate::blake2b::blake2b_chip::{Blake2bChip, Blake2bConfig, RangeCheckTable};
+use midnight_proofs::circuit::SimpleFloorPlanner;
+use midnight_proofs::plonk::{Circuit, Instance};
+use std::array;
+
+/// How the chip gets its columns and range-check table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ChipColumns {
+    /// The chip configures its columns and creates the given range-check table.
+    Owned(RangeCheckTable),
+    /// The circuit owns all the columns and the 8-bit range table, as a circuit that combines the
+    /// chip with other gadgets would, and lends them to the chip.
+    Shared {
+        /// Whether the circuit fills the range table, which the chip leaves to its owner.
+        fill_range_table: bool,
+    },
+}
+
+impl ChipColumns {
+    fn fills_range_table(self) -> bool {
+        match self {
+            ChipColumns::Owned(_) => false,
+            ChipColumns::Shared { fill_range_table } => fill_range_table,
+        }
+    }
+}
+
+impl Default for ChipColumns {
+    fn default() -> Self {
+        ChipColumns::Owned(RangeCheckTable::default())
+    }
+}
+
+/// A circuit that hashes 'input', without a key, into a digest of 'output_size' bytes in its
+/// instance column.
+pub(super) struct ChipTestCircuit {
+    pub(super) input: Vec<u8>,
+    pub(super) output_size: usize,
+    pub(super) columns: ChipColumns,
+}
+
+#[derive(Clone)]
+pub(super) struct ChipTestConfig {
+    blake2b_config: Blake2bConfig,
+    advice_columns: [Column<Advice>; 9],
+    /// The range table of the circuit, when it lends one to the chip.
+    range_table: Option<TableColumn>,
+    instance: Column<Instance>,
+}
+
+impl Circuit<Fq> for ChipTestCircuit {
+    type Config = ChipTestConfig;
+    type Params = ChipColumns;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            input: self.input.clone(),
+            output_size: self.output_size,
+            columns: self.columns,
+        }
+    }
+
+    fn params(&self) -> Self::Params {
+        self.columns
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        Self::configure_with_params(meta, ChipColumns::default())
+    }
+
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<Fq>,
+        columns: Self::Params,
+    ) -> Self::Config {
+        let advice_columns: [Column<Advice>; 9] = array::from_fn(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        let full_number_u64 = advice_columns[0];
+        let limbs = advice_columns[1..].try_into().unwrap();
+
+        let (blake2b_config, range_table) = match columns {
+            ChipColumns::Owned(range_check_table) => {
+                let config = Blake2bChip::configure_with_range_check_table(
+                    meta,
+                    constants,
+                    full_number_u64,
+                    limbs,
+                    range_check_table,
+                );
+                (config, None)
+            }
+            ChipColumns::Shared { .. } => {
+                for column in advice_columns {
+                    meta.enable_equality(column);
+                }
+                meta.enable_constant(constants);
+                let range_table = meta.lookup_table_column();
+                let config = Blake2bChip::configure_with_shared_columns(
+                    meta,
+                    full_number_u64,
+                    limbs,
+                    range_table,
+                );
+                (config, Some(range_table))
+            }
+        };
+
+        ChipTestConfig {
+            blake2b_config,
+            advice_columns,
+            range_table,
+            instance,
+        }
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        if let Some(range_table) = config.range_table.filter(|_| self.columns.fills_range_table()) {
+            populate_lookup_table(&mut layouter, range_table)?;
+        }
+
+        let input = layouter.assign_region(
+            || "Inputs",
+            |mut region| {
+                self.input
+                    .iter()
+                    .enumerate()
+                    .map(|(index, byte)| {
+                        region.assign_advice(
+                            || "input byte",
+                            config.advice_columns[index % 9],
+                            index / 9,
+                            || Value::known(Fq::from(*byte as u64)),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let chip = Blake2bChip::new(&config.blake2b_config);
+        chip.load(&mut layouter)?;
+        let result = chip.hash(&mut layouter, &input, &[], self.output_size)?;
+        for (i, byte) in result.iter().enumerate().take(self.output_size) {
+            layouter.constrain_instance(byte.cell(), config.instance, i)?;
+        }
+        Ok(())
+    }
+}
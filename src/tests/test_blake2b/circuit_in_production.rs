@@ -1,5 +1,7 @@
-use super::*;
-use crate::usage_utils::circuit_runner::CircuitRunner;
+use crate::error::Blake2bError;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use midnight_curves::bls12_381::Bls12;
+use midnight_proofs::poly::kzg::params::ParamsKZG;
 
 #[test]
 fn test_with_real_snark() {
@@ -11,15 +13,64 @@ fn test_with_real_snark() {
 }
 
 #[test]
-#[should_panic]
 fn test_negative_with_real_snark() {
     let input = String::from("0001");
     let out = String::from("2c08798dc641aba9dee435e22519a4729a09b2bfe0ff00ef2dcd8ed6f8a07d15eaf4aee52bbf18ab5608a6190f70b90486c8a7d4873710b1115d3debbb4327b5");
     let key = String::from("");
 
-    assert!(test_in_production(input, out, key).is_ok());
+    assert!(test_in_production(input, out, key).is_err());
+}
+
+#[test]
+fn test_proof_verifies_with_params_bigger_than_the_circuit() {
+    let input = String::from("0001");
+    let out = String::from("1c08798dc641aba9dee435e22519a4729a09b2bfe0ff00ef2dcd8ed6f8a07d15eaf4aee52bbf18ab5608a6190f70b90486c8a7d4873710b1115d3debbb4327b5");
+    let circuit_inputs =
+        Bls12CircuitRunner::prepare_parameters_for_test(&input, &String::new(), &out).unwrap();
+    let circuit =
+        Bls12CircuitRunner::create_circuit_for_packed_inputs(circuit_inputs.clone()).unwrap();
+
+    let k = circuit.k() + 1;
+    let params = ParamsKZG::<Bls12>::unsafe_setup(k, &mut rand::thread_rng());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    assert_eq!(vk.get_domain().k(), k);
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+    let proof =
+        Bls12CircuitRunner::create_proof(&circuit_inputs.4, circuit, &params, &pk).unwrap().proof;
+
+    Bls12CircuitRunner::verify_with_vk(&vk, &params, &circuit_inputs.4, &proof).unwrap();
+}
+
+#[test]
+fn test_vk_is_not_created_with_params_smaller_than_the_circuit() {
+    let circuit_inputs = Bls12CircuitRunner::prepare_parameters_for_test(
+        &String::from("0001"),
+        &String::new(),
+        &"00".repeat(64),
+    )
+    .unwrap();
+    let circuit = Bls12CircuitRunner::create_circuit_for_packed_inputs(circuit_inputs).unwrap();
+    let k = circuit.k();
+
+    let params = ParamsKZG::<Bls12>::unsafe_setup(k - 1, &mut rand::thread_rng());
+    let result = Bls12CircuitRunner::create_vk(&circuit, &params);
+
+    assert!(matches!(result, Err(Blake2bError::ParamsTooSmall(params_k, circuit_k))
+        if params_k == k - 1 && circuit_k == k));
+}
+
+#[test]
+fn test_invalid_hex_is_an_error_instead_of_a_panic() {
+    let digest = "00".repeat(64);
+
+    let result = test_in_production(String::from("0g"), digest.clone(), String::new());
+    assert!(matches!(result, Err(Blake2bError::InvalidHex(_))));
+    let result = test_in_production(String::from("000"), digest, String::new());
+    assert!(matches!(result, Err(Blake2bError::InvalidHex(_))));
+    let result = test_in_production(String::from("00"), "00".repeat(65), String::new());
+    assert!(matches!(result, Err(Blake2bError::InvalidOutputSize(65))));
 }
 
-fn test_in_production(input: String, out: String, key: String) -> Result<(), Error> {
-    CircuitRunner::real_preprocess_inputs_synthesize_prove_and_verify(input, out, key)
+fn test_in_production(input: String, out: String, key: String) -> Result<(), Blake2bError> {
+    Bls12CircuitRunner::real_preprocess_inputs_synthesize_prove_and_verify(input, out, key)
 }
@@ -0,0 +1,121 @@
+use super::*;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, Blake2bInstanceMode};
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+
+fn circuit_for(message: &[u8], instance_mode: Blake2bInstanceMode) -> Blake2bCircuit<Fq> {
+    Blake2bCircuit::new_with_instance_mode(
+        message.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect(),
+        message.len(),
+        vec![],
+        0,
+        64,
+        instance_mode,
+    )
+    .unwrap()
+}
+
+fn fields_for(bytes: &[u8]) -> Vec<Fq> {
+    bytes.iter().map(|byte| Fq::from(*byte as u64)).collect()
+}
+
+#[test]
+fn test_mocked_committed_message_is_constrained_to_the_input() {
+    let message = b"committed message";
+    let circuit = circuit_for(message, Blake2bInstanceMode::CommittedMessage);
+    let digest = expected_output_for(message, 64);
+
+    let prover = Bls12CircuitRunner::mock_prove_with_instances(
+        vec![fields_for(message), digest.clone()],
+        &circuit,
+    )
+    .unwrap();
+    assert!(prover.verify().is_ok());
+
+    let prover = Bls12CircuitRunner::mock_prove_with_instances(
+        vec![fields_for(b"committed massage"), digest],
+        &circuit,
+    )
+    .unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_committed_digest_verifies_only_against_its_commitment() {
+    let message = b"committed digest";
+    let circuit = circuit_for(message, Blake2bInstanceMode::CommittedDigest);
+    let digest = expected_output_for(message, 64);
+
+    let params = Bls12CircuitRunner::create_params(circuit.k());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+    let artifacts =
+        Bls12CircuitRunner::create_proof_with_instances(&[&digest], circuit, &params, &pk).unwrap();
+    // The digest is only known to the verifier through its commitment
+    assert!(artifacts.public_inputs.is_empty());
+    let proof = artifacts.proof;
+
+    let commitment = Bls12CircuitRunner::commit_to_instance(&vk, &params, &digest);
+    assert!(Bls12CircuitRunner::verify_with_committed_instances(
+        &vk,
+        &params,
+        &[commitment],
+        &[],
+        &proof
+    )
+    .is_ok());
+
+    let other_commitment = Bls12CircuitRunner::commit_to_instance(
+        &vk,
+        &params,
+        &expected_output_for(b"another digest", 64),
+    );
+    assert!(Bls12CircuitRunner::verify_with_committed_instances(
+        &vk,
+        &params,
+        &[other_commitment],
+        &[],
+        &proof
+    )
+    .is_err());
+}
+
+#[test]
+fn test_committed_message_verifies_only_against_its_commitment() {
+    let message = b"committed message";
+    let circuit = circuit_for(message, Blake2bInstanceMode::CommittedMessage);
+    let message_fields = fields_for(message);
+    let digest = expected_output_for(message, 64);
+
+    let params = Bls12CircuitRunner::create_params(circuit.k());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+    let proof = Bls12CircuitRunner::create_proof_with_instances(
+        &[&message_fields, &digest],
+        circuit,
+        &params,
+        &pk,
+    )
+    .unwrap()
+    .proof;
+
+    let commitment = Bls12CircuitRunner::commit_to_instance(&vk, &params, &message_fields);
+    assert!(Bls12CircuitRunner::verify_with_committed_instances(
+        &vk,
+        &params,
+        &[commitment],
+        &[&digest],
+        &proof
+    )
+    .is_ok());
+
+    let other_commitment =
+        Bls12CircuitRunner::commit_to_instance(&vk, &params, &fields_for(b"committed massage"));
+    assert!(Bls12CircuitRunner::verify_with_committed_instances(
+        &vk,
+        &params,
+        &[other_commitment],
+        &[&digest],
+        &proof
+    )
+    .is_err());
+}
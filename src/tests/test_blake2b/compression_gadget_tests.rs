@@ -0,0 +1,413 @@
+use super::*;
+use crate::blake2b::blake2b_chip::{Blake2bChip, Blake2bConfig};
+use crate::blake2b::compression_gadget::{Blake2bCompressionGadget, Blake2bCounter};
+use crate::blake2b::utils::{ABCD, IV_CONSTANTS, SIGMA};
+use crate::error::Blake2bError;
+use midnight_proofs::circuit::{Region, SimpleFloorPlanner};
+use midnight_proofs::dev::MockProver;
+use midnight_proofs::plonk::{Circuit, Instance};
+use std::array;
+
+/// One compression of a chain: the block, the counter and the finalization flags.
+#[derive(Clone)]
+struct Compression {
+    block: [u64; 16],
+    counter: u128,
+    f0: bool,
+    f1: bool,
+}
+
+/// A circuit that chains compressions over an initial chaining value given as field elements, and
+/// exposes the last chaining value as 8 public words.
+#[derive(Clone)]
+struct CompressionCircuit {
+    chaining_value: [Fq; 8],
+    compressions: Vec<Compression>,
+    witness_counter: bool,
+}
+
+/// A circuit that computes G over witness words and exposes the new (a, b, c, d) as public words.
+#[derive(Clone)]
+struct MixCircuit {
+    v: [u64; 4],
+    x: u64,
+    y: u64,
+}
+
+fn configure_gadget(meta: &mut ConstraintSystem<Fq>) -> (Blake2bConfig, Column<Instance>) {
+    let full_number_u64 = meta.advice_column();
+    let limbs: [Column<Advice>; 8] = array::from_fn(|_| meta.advice_column());
+    let constants = meta.fixed_column();
+    let instance = meta.instance_column();
+    meta.enable_equality(instance);
+    (Blake2bChip::configure(meta, constants, full_number_u64, limbs), instance)
+}
+
+/// Assigns 'values' in the limb columns, 8 per row, starting at '*offset'.
+fn assign_words<const N: usize>(
+    region: &mut Region<'_, Fq>,
+    config: &Blake2bConfig,
+    offset: &mut usize,
+    values: [Fq; N],
+) -> Result<[AssignedNative<Fq>; N], Error> {
+    let cells = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            region.assign_advice(
+                || "word",
+                config.limbs[index % 8],
+                *offset + index / 8,
+                || Value::known(*value),
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    *offset += N.div_ceil(8);
+    Ok(cells.try_into().unwrap())
+}
+
+impl Circuit<Fq> for CompressionCircuit {
+    type Config = (Blake2bConfig, Column<Instance>);
+    type Params = ();
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        configure_gadget(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let (mut chaining_value, inputs) = layouter.assign_region(
+            || "Inputs",
+            |mut region| {
+                let mut offset = 0;
+                let chaining_value =
+                    assign_words(&mut region, &config.0, &mut offset, self.chaining_value)?;
+                let inputs = self
+                    .compressions
+                    .iter()
+                    .map(|compression| {
+                        let block = assign_words(
+                            &mut region,
+                            &config.0,
+                            &mut offset,
+                            compression.block.map(Fq::from),
+                        )?;
+                        let counter = if self.witness_counter {
+                            let words =
+                                [compression.counter as u64, (compression.counter >> 64) as u64];
+                            Blake2bCounter::Witness(assign_words(
+                                &mut region,
+                                &config.0,
+                                &mut offset,
+                                words.map(Fq::from),
+                            )?)
+                        } else {
+                            Blake2bCounter::Constant(compression.counter)
+                        };
+                        Ok((block, counter))
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                Ok((chaining_value, inputs))
+            },
+        )?;
+
+        let gadget = Blake2bCompressionGadget::new(&config.0);
+        gadget.load(&mut layouter)?;
+        for ((block, counter), compression) in inputs.iter().zip(&self.compressions) {
+            chaining_value = gadget.compress(
+                &mut layouter,
+                &chaining_value,
+                block,
+                counter,
+                compression.f0,
+                compression.f1,
+            )?;
+        }
+        for (i, word) in chaining_value.iter().enumerate() {
+            layouter.constrain_instance(word.cell(), config.1, i)?;
+        }
+        Ok(())
+    }
+}
+
+impl Circuit<Fq> for MixCircuit {
+    type Config = (Blake2bConfig, Column<Instance>);
+    type Params = ();
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        self.clone()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        configure_gadget(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let [a, b, c, d, x, y] = layouter.assign_region(
+            || "Inputs",
+            |mut region| {
+                let [a, b, c, d] = self.v;
+                let words = [a, b, c, d, self.x, self.y].map(Fq::from);
+                assign_words(&mut region, &config.0, &mut 0, words)
+            },
+        )?;
+
+        let gadget = Blake2bCompressionGadget::new(&config.0);
+        gadget.load(&mut layouter)?;
+        let result = gadget.mix(&mut layouter, &[a, b, c, d], &x, &y)?;
+        for (i, word) in result.iter().enumerate() {
+            layouter.constrain_instance(word.cell(), config.1, i)?;
+        }
+        Ok(())
+    }
+}
+
+/// The chaining value of an unkeyed hash with a 64-byte digest before its first compression.
+fn initial_chaining_value() -> [u64; 8] {
+    let mut chaining_value = IV_CONSTANTS;
+    chaining_value[0] ^= 0x01010040;
+    chaining_value
+}
+
+fn words_of_block(block: &[u8]) -> [u64; 16] {
+    let mut padded = [0u8; 128];
+    padded[..block.len()].copy_from_slice(block);
+    array::from_fn(|i| u64::from_le_bytes(padded[8 * i..8 * (i + 1)].try_into().unwrap()))
+}
+
+/// The words of a 64-byte digest, which are the chaining value after the last compression.
+fn digest_words(digest: &[u8]) -> Vec<Fq> {
+    digest.chunks(8).map(|word| Fq::from(u64::from_le_bytes(word.try_into().unwrap()))).collect()
+}
+
+/// The G function of RFC 7693, section 3.1.
+fn reference_mix([mut a, mut b, mut c, mut d]: [u64; 4], x: u64, y: u64) -> [u64; 4] {
+    a = a.wrapping_add(b).wrapping_add(x);
+    d = (d ^ a).rotate_right(32);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(24);
+    a = a.wrapping_add(b).wrapping_add(y);
+    d = (d ^ a).rotate_right(16);
+    c = c.wrapping_add(d);
+    b = (b ^ c).rotate_right(63);
+    [a, b, c, d]
+}
+
+/// The compression function F of RFC 7693, section 3.2.
+fn reference_compress(
+    chaining_value: [u64; 8],
+    block: [u64; 16],
+    counter: u128,
+    f0: bool,
+    f1: bool,
+) -> [u64; 8] {
+    let mut v: [u64; 16] =
+        array::from_fn(|i| if i < 8 { chaining_value[i] } else { IV_CONSTANTS[i - 8] });
+    v[12] ^= counter as u64;
+    v[13] ^= (counter >> 64) as u64;
+    if f0 {
+        v[14] = !v[14];
+    }
+    if f1 {
+        v[15] = !v[15];
+    }
+    for sigma in SIGMA {
+        for (mix, [a, b, c, d]) in ABCD.into_iter().enumerate() {
+            let (x, y) = (block[sigma[2 * mix]], block[sigma[2 * mix + 1]]);
+            [v[a], v[b], v[c], v[d]] = reference_mix([v[a], v[b], v[c], v[d]], x, y);
+        }
+    }
+    array::from_fn(|i| chaining_value[i] ^ v[i] ^ v[i + 8])
+}
+
+/// A chaining value and a block that are not those of any hash of a short message.
+fn arbitrary_chaining_value_and_block() -> ([u64; 8], [u64; 16]) {
+    let chaining_value =
+        array::from_fn(|i| 0x0123456789abcdef_u64.rotate_left(8 * i as u32) ^ i as u64);
+    let block = array::from_fn(|i| u64::MAX.wrapping_sub(0x1111111111111111 * i as u64));
+    (chaining_value, block)
+}
+
+#[test]
+fn test_single_compression_of_the_rfc_abc_vector() {
+    // Appendix A of RFC 7693: "abc" fits in a single block, which is the last one.
+    let expected_output = digest_words(blake2b_simd::blake2b(b"abc").as_bytes());
+    for witness_counter in [false, true] {
+        let circuit = CompressionCircuit {
+            chaining_value: initial_chaining_value().map(Fq::from),
+            compressions: vec![Compression {
+                block: words_of_block(b"abc"),
+                counter: 3,
+                f0: true,
+                f1: false,
+            }],
+            witness_counter,
+        };
+        let prover = MockProver::run(&circuit, vec![expected_output.clone()]).unwrap();
+        prover.verify().unwrap();
+    }
+}
+
+#[test]
+fn test_reference_compression_matches_the_hash() {
+    let expected_output = blake2b_simd::blake2b(b"abc");
+    let output =
+        reference_compress(initial_chaining_value(), words_of_block(b"abc"), 3, true, false);
+    assert_eq!(output.map(Fq::from).to_vec(), digest_words(expected_output.as_bytes()));
+}
+
+#[test]
+fn test_single_compression_from_a_chaining_value_that_is_not_the_iv() {
+    let (chaining_value, block) = arbitrary_chaining_value_and_block();
+    let expected_output = reference_compress(chaining_value, block, 1 << 40, false, false);
+    for witness_counter in [false, true] {
+        let circuit = CompressionCircuit {
+            chaining_value: chaining_value.map(Fq::from),
+            compressions: vec![Compression {
+                block,
+                counter: 1 << 40,
+                f0: false,
+                f1: false,
+            }],
+            witness_counter,
+        };
+        let prover =
+            MockProver::run(&circuit, vec![expected_output.map(Fq::from).to_vec()]).unwrap();
+        prover.verify().unwrap();
+    }
+}
+
+#[test]
+fn test_single_compression_with_a_counter_of_more_than_64_bits() {
+    let (chaining_value, block) = arbitrary_chaining_value_and_block();
+    for counter in [1 << 64, (0xfedcba9876543210 << 64) + 0x0123456789abcdef, u128::MAX] {
+        let expected_output = reference_compress(chaining_value, block, counter, true, false);
+        // Only the high word of the counter tells these outputs apart
+        let low_word_output =
+            reference_compress(chaining_value, block, counter as u64 as u128, true, false);
+        assert_ne!(expected_output, low_word_output);
+
+        for witness_counter in [false, true] {
+            let circuit = CompressionCircuit {
+                chaining_value: chaining_value.map(Fq::from),
+                compressions: vec![Compression {
+                    block,
+                    counter,
+                    f0: true,
+                    f1: false,
+                }],
+                witness_counter,
+            };
+            let prover =
+                MockProver::run(&circuit, vec![expected_output.map(Fq::from).to_vec()]).unwrap();
+            prover.verify().unwrap();
+
+            let prover =
+                MockProver::run(&circuit, vec![low_word_output.map(Fq::from).to_vec()]).unwrap();
+            assert!(prover.verify().is_err());
+        }
+    }
+}
+
+#[test]
+fn test_chained_compressions_match_the_hash() {
+    let message: Vec<u8> = (0..200).map(|i| (i * 7 % 256) as u8).collect();
+    let circuit = CompressionCircuit {
+        chaining_value: initial_chaining_value().map(Fq::from),
+        compressions: vec![
+            Compression {
+                block: words_of_block(&message[..128]),
+                counter: 128,
+                f0: false,
+                f1: false,
+            },
+            Compression {
+                block: words_of_block(&message[128..]),
+                counter: 200,
+                f0: true,
+                f1: false,
+            },
+        ],
+        witness_counter: true,
+    };
+
+    let expected_output = digest_words(blake2b_simd::blake2b(&message).as_bytes());
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    prover.verify().unwrap();
+}
+
+#[test]
+fn test_compression_with_both_finalization_flags() {
+    let compression = Compression {
+        block: words_of_block(b"last node"),
+        counter: 9,
+        f0: true,
+        f1: true,
+    };
+    let circuit = CompressionCircuit {
+        chaining_value: initial_chaining_value().map(Fq::from),
+        compressions: vec![compression.clone()],
+        witness_counter: false,
+    };
+    let digest = blake2b_simd::Params::new().last_node(true).hash(b"last node");
+    let expected_output = digest_words(digest.as_bytes());
+    let prover = MockProver::run(&circuit, vec![expected_output.clone()]).unwrap();
+    prover.verify().unwrap();
+
+    // Without f1 the compression is the one of a regular hash, whose result is different.
+    let circuit = CompressionCircuit {
+        compressions: vec![Compression {
+            f1: false,
+            ..compression
+        }],
+        ..circuit
+    };
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_mix_matches_the_reference_g() {
+    let v = [IV_CONSTANTS[0], IV_CONSTANTS[4], IV_CONSTANTS[1], u64::MAX];
+    let (x, y) = (0x0123456789abcdef, 0xfedcba9876543210);
+    let circuit = MixCircuit { v, x, y };
+
+    let expected_output = reference_mix(v, x, y).map(Fq::from).to_vec();
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    prover.verify().unwrap();
+}
+
+#[test]
+fn test_chaining_value_that_is_not_a_word_is_rejected() {
+    let mut chaining_value = initial_chaining_value().map(Fq::from);
+    chaining_value[3] = Fq::from_u128(1 << 64);
+    let circuit = CompressionCircuit {
+        chaining_value,
+        compressions: vec![Compression {
+            block: words_of_block(b"abc"),
+            counter: 3,
+            f0: true,
+            f1: false,
+        }],
+        witness_counter: false,
+    };
+
+    let result = MockProver::run(&circuit, vec![vec![Fq::ZERO; 8]]);
+    assert!(matches!(
+        result,
+        Err(Error::Synthesis(message)) if message == Blake2bError::NonWordInput.to_string()
+    ));
+}
@@ -0,0 +1,96 @@
+//! The constraints that touch the advice cells of the hash of "abc", as found by
+//! [crate::usage_utils::constraint_coverage].
+
+use crate::usage_utils::constraint_coverage::{constraint_coverage, CellCoverage};
+use crate::usage_utils::layout::AdviceCell;
+
+/// The coverage of the hash of "abc".
+fn abc_constraint_coverage() -> Vec<(AdviceCell, CellCoverage)> {
+    constraint_coverage(3, 0, 64).unwrap().cells
+}
+
+fn coverage_of_cells_annotated<'a>(
+    coverage: &'a [(AdviceCell, CellCoverage)],
+    annotation: &'a str,
+) -> impl Iterator<Item = &'a CellCoverage> {
+    let cells: Vec<_> = coverage
+        .iter()
+        .filter(move |(cell, _)| cell.annotation == annotation)
+        .map(|(_, cell_coverage)| cell_coverage)
+        .collect();
+    assert!(!cells.is_empty(), "no advice cell is annotated '{annotation}'");
+    cells.into_iter()
+}
+
+#[test]
+fn test_every_advice_cell_is_constrained() {
+    let coverage = constraint_coverage(3, 0, 64).unwrap();
+
+    let unconstrained = coverage.unconstrained_cells();
+    assert!(
+        unconstrained.is_empty(),
+        "{} of {} advice cells are unconstrained: {unconstrained:?}",
+        unconstrained.len(),
+        coverage.cells.len()
+    );
+}
+
+/// Every kind of cell, i.e. every (annotation, column), is touched by some constraint, and the
+/// table of the kinds lists them all.
+#[test]
+fn test_every_kind_of_cell_is_constrained() {
+    let coverage = constraint_coverage(3, 0, 64).unwrap();
+    let kinds = coverage.by_kind();
+    let table = coverage.to_table();
+
+    assert!(kinds.keys().any(|(annotation, _)| annotation == "limb"));
+    for ((annotation, column), kind) in &kinds {
+        assert!(!kind.is_unconstrained(), "'{annotation}' at column {column} is unconstrained");
+        assert!(table.contains(&format!("'{annotation}' at column {column}: ")));
+    }
+    assert_eq!(table.lines().count(), kinds.len());
+}
+
+#[test]
+fn test_limbs_are_decomposed_and_range_checked() {
+    let coverage = abc_constraint_coverage();
+    for annotation in ["limb", "Copied input byte"] {
+        for cell_coverage in coverage_of_cells_annotated(&coverage, annotation) {
+            assert!(cell_coverage.gates.contains("decompose in 8 bit words"));
+            assert!(!cell_coverage.lookups.is_empty());
+        }
+    }
+}
+
+/// The limbs of a rotation output are not range-checked where they are assigned, but they are
+/// copies of range-checked limbs.
+#[test]
+fn test_rotation_output_limbs_are_decomposed_copies() {
+    let coverage = abc_constraint_coverage();
+    for cell_coverage in coverage_of_cells_annotated(&coverage, "Limb rotation output") {
+        assert!(cell_coverage.gates.contains("decompose in 8 bit words"));
+        assert!(cell_coverage.lookups.is_empty());
+        assert!(cell_coverage.copies > 0);
+    }
+}
+
+#[test]
+fn test_full_numbers_are_decomposed() {
+    let coverage = abc_constraint_coverage();
+    for annotation in ["full number", "Full number rotation output"] {
+        for cell_coverage in coverage_of_cells_annotated(&coverage, annotation) {
+            assert!(cell_coverage.gates.contains("decompose in 8 bit words"));
+        }
+    }
+}
+
+/// The negation output is only constrained by the negate gate and the copies of its value. It is
+/// never range-checked where it is assigned, so its soundness relies on the row it is copied to.
+#[test]
+fn test_negation_output_is_only_arithmetically_constrained() {
+    let coverage = abc_constraint_coverage();
+    for cell_coverage in coverage_of_cells_annotated(&coverage, "Negation output") {
+        assert!(cell_coverage.gates.contains("negate"));
+        assert!(cell_coverage.lookups.is_empty());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use crate::usage_utils::cost_report::cost_report;
+use midnight_curves::bls12_381::Fq;
+
+#[test]
+fn test_cost_report_of_a_keyed_hash() {
+    let report = cost_report(300, 17, 32).unwrap();
+
+    assert_eq!(report.blocks, 4);
+    assert_eq!(report.k, 17);
+    assert_eq!(report.advice_rows, Blake2bCircuit::<Fq>::rows_required(300, 17));
+    assert_eq!(report.advice_rows_per_block, 2465);
+    assert_eq!(report.table_rows, 1 << 16);
+    assert_eq!(report.advice_columns, 9);
+    assert_eq!(report.instance_columns, 1);
+    assert_eq!(report.lookup_arguments["range"], 8);
+    assert_eq!(report.lookup_arguments["xor"], 8);
+    // Every block range-checks 688 rows and looks up the xors of 400 (384 in the mixes and 16
+    // in the finalization)
+    assert_eq!(report.lookup_rows["range"], 4 * 688);
+    assert_eq!(report.lookup_rows["xor"], 4 * 400);
+}
+
+#[test]
+fn test_cost_report_as_json() {
+    let report = cost_report(0, 0, 64).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+
+    assert_eq!(json["blocks"], 1);
+    assert_eq!(json["advice_rows"], 2469);
+    assert_eq!(json["lookup_arguments"]["xor"], 8);
+    assert_eq!(json["lookup_rows"]["xor"], 400);
+    assert_eq!(json["proof_size"], report.proof_size);
+}
+
+#[test]
+fn test_cost_report_rejects_invalid_sizes() {
+    assert!(matches!(cost_report(0, 0, 65), Err(Blake2bError::InvalidOutputSize(65))));
+    assert!(matches!(cost_report(0, 65, 64), Err(Blake2bError::InvalidKeySize(65))));
+}
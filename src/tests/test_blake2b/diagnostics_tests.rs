@@ -0,0 +1,127 @@
+use super::recorded_layout::{
+    abc_circuit_and_instance, mock_prover_failures, recorded_layout, AdviceCell, Mutation,
+    RecordedCircuit,
+};
+use super::*;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, PublicInputBytes};
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use crate::usage_utils::diagnostics::{Blake2bDiagnostics, FailureDiagnostic};
+use crate::usage_utils::layout::{circuit_layout, circuit_layout_of, CircuitLayout, LayoutRow};
+use midnight_proofs::plonk::{Circuit, ConstraintSystem};
+
+/// The last row of the layout of "abc" labelled with the given mix and operation, which is the
+/// result row of the operation.
+fn result_row_of(round: usize, mix: usize, operation: &str) -> LayoutRow {
+    result_row_in(circuit_layout(3, 0, 64).unwrap(), round, mix, operation)
+}
+
+fn result_row_in(layout: CircuitLayout, round: usize, mix: usize, operation: &str) -> LayoutRow {
+    layout
+        .rows
+        .into_iter()
+        .rev()
+        .find(|row| row.round == Some(round) && row.mix == Some(mix) && row.operation == operation)
+        .unwrap()
+}
+
+fn diagnostics_of_tampering(cell: &AdviceCell) -> Vec<FailureDiagnostic> {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let failures = mock_prover_failures(&circuit, &instance, &Mutation::add_one(cell));
+    assert!(!failures.is_empty(), "the mutation of {cell:?} went undetected");
+    Blake2bDiagnostics::new(3, 0, 64).unwrap().diagnose_all(&failures)
+}
+
+fn cell_at(row: usize, column: usize, annotation: &str) -> AdviceCell {
+    let (circuit, instance) = abc_circuit_and_instance();
+    cell_of(&circuit, &instance, row, column, annotation)
+}
+
+fn cell_of(
+    circuit: &RecordedCircuit,
+    instance: &[Fq],
+    row: usize,
+    column: usize,
+    annotation: &str,
+) -> AdviceCell {
+    recorded_layout(circuit, instance)
+        .advice_cells
+        .into_iter()
+        .find(|cell| cell.row == row && cell.column == column && cell.annotation == annotation)
+        .unwrap_or_else(|| panic!("no '{annotation}' cell at column {column}, row {row}"))
+}
+
+#[test]
+fn test_honest_witness_has_no_diagnostics() {
+    let input = b"abc";
+    let input_values = input.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect();
+    let circuit =
+        Bls12CircuitRunner::create_circuit_for_inputs(input_values, 3, vec![], 0, 64).unwrap();
+    let prover = Bls12CircuitRunner::mock_prove_with_public_inputs_ref(
+        &expected_output_for(input, 64),
+        &circuit,
+    )
+    .unwrap();
+
+    assert_eq!(Blake2bDiagnostics::new(3, 0, 64).unwrap().verify(&prover), Ok(()));
+}
+
+#[test]
+fn test_tampered_xor_result_limb_is_reported_in_its_mix() {
+    let mut meta = ConstraintSystem::<Fq>::default();
+    let limb_4 = Blake2bCircuit::<Fq>::configure(&mut meta).0.limbs[4].index();
+    let row = result_row_of(5, 3, "b = b ^ c");
+
+    let diagnostics = diagnostics_of_tampering(&cell_at(row.row, limb_4, "limb"));
+    let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+
+    assert!(
+        messages.contains(&"block 0, round 5, G3 (b = b ^ c): xor lookup failed on limb 4".into()),
+        "{messages:#?}"
+    );
+    assert!(diagnostics.iter().all(|d| d.block == Some(0)), "{messages:#?}");
+}
+
+#[test]
+fn test_tampered_carry_is_reported_as_a_failed_addition() {
+    let row = result_row_of(2, 6, "c = c + d");
+    let (circuit, instance) = abc_circuit_and_instance();
+    // The carry is assigned in the row of the second operand, right before the result.
+    let carry = recorded_layout(&circuit, &instance)
+        .advice_cells
+        .into_iter()
+        .find(|cell| cell.row == row.row - 1 && cell.annotation == "carry")
+        .unwrap();
+
+    let diagnostics = diagnostics_of_tampering(&carry);
+
+    let addition = diagnostics
+        .iter()
+        .find(|d| d.description == "gate 'sum mod 2 ^ 64' is not satisfied")
+        .unwrap_or_else(|| panic!("{diagnostics:#?}"));
+    assert_eq!((addition.block, addition.round, addition.mix), (Some(0), Some(2), Some(6)));
+    assert_eq!(addition.operation, "c = c + d");
+}
+
+#[test]
+fn test_failures_are_diagnosed_with_the_layout_of_the_circuit_that_failed() {
+    let (RecordedCircuit(circuit), instance) = abc_circuit_and_instance();
+    // The fixed input bytes add a region before the one of the chip
+    let fixed_prefix = PublicInputBytes::Fixed {
+        offset: 0,
+        bytes: b"ab".to_vec(),
+    };
+    let circuit = RecordedCircuit(circuit.with_public_input(vec![fixed_prefix]).unwrap());
+    let mut meta = ConstraintSystem::<Fq>::default();
+    let limb_4 = Blake2bCircuit::<Fq>::configure(&mut meta).0.limbs[4].index();
+    let row = result_row_in(circuit_layout_of(&circuit.0).unwrap(), 5, 3, "b = b ^ c");
+    let cell = cell_of(&circuit, &instance, row.row, limb_4, "limb");
+
+    let failures = mock_prover_failures(&circuit, &instance, &Mutation::add_one(&cell));
+    let diagnostics = Blake2bDiagnostics::for_circuit(&circuit.0).unwrap().diagnose_all(&failures);
+    let messages: Vec<String> = diagnostics.iter().map(|d| d.to_string()).collect();
+
+    assert!(
+        messages.contains(&"block 0, round 5, G3 (b = b ^ c): xor lookup failed on limb 4".into()),
+        "{messages:#?}"
+    );
+}
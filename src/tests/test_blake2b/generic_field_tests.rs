@@ -0,0 +1,31 @@
+use crate::error::Blake2bError;
+use crate::usage_utils::circuit_runner::CircuitRunner;
+use midnight_curves::curve25519::Fp as Curve25519Base;
+
+/// The runner isn't tied to BLS12-381: the circuit is synthesized and checked by the Mock Prover
+/// over the base field of Curve25519. The commitment scheme is irrelevant for the Mock Prover.
+type Curve25519CircuitRunner = CircuitRunner<Curve25519Base, ()>;
+
+#[test]
+fn test_blake2b_over_another_field_positive() {
+    let input = String::from("616263");
+    let out = String::from("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+    let key = String::from("");
+
+    Curve25519CircuitRunner::mocked_preprocess_inputs_synthesize_prove_and_verify(
+        &input, &key, &out,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_blake2b_over_another_field_negative() {
+    let input = String::from("616263");
+    let out = String::from("ca80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923");
+    let key = String::from("");
+
+    let result = Curve25519CircuitRunner::mocked_preprocess_inputs_synthesize_prove_and_verify(
+        &input, &key, &out,
+    );
+    assert!(matches!(result, Err(Blake2bError::Unsatisfied(_))));
+}
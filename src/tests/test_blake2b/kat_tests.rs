@@ -0,0 +1,131 @@
+//! Known-answer tests. 'test_vector.json' is the BLAKE2b section of the KATs of the BLAKE2
+//! reference implementation, and its cases are run by [super::vector_tests].
+//! 'test_vector_extended.json', in the same format, adds the output and key sizes those KATs don't
+//! cover, generated with an independent reference implementation, along with salted and
+//! personalized cases. Only the cases that aren't already in 'test_vector.json' are run here. The
+//! cases of any mode listed in 'kat_unsupported.json' are skipped, and any other mode makes the
+//! tests fail.
+
+use super::vector_tests::run_test;
+use serde::Deserialize;
+use std::collections::BTreeSet;
+
+const TEST_VECTOR: &str = "./test_vector.json";
+const EXTENDED_KAT: &str = "./test_vector_extended.json";
+const UNSUPPORTED_MODES: &str = "./kat_unsupported.json";
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct KatCase {
+    hash: String,
+    #[serde(rename = "in")]
+    input: String,
+    key: String,
+    #[serde(default)]
+    salt: Option<String>,
+    #[serde(default)]
+    personal: Option<String>,
+    out: String,
+}
+
+impl KatCase {
+    /// The modes needed to compute the case: the hash function, plus the parameters of the
+    /// parameter block it sets besides the key and output sizes.
+    fn modes(&self) -> Vec<String> {
+        let mut modes = vec![self.hash.clone()];
+        if self.salt.is_some() {
+            modes.push(format!("{}+salt", self.hash));
+        }
+        if self.personal.is_some() {
+            modes.push(format!("{}+personal", self.hash));
+        }
+        modes
+    }
+
+    fn is_supported(&self) -> bool {
+        self.modes() == ["blake2b"]
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct UnsupportedMode {
+    mode: String,
+    reason: String,
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &str) -> T {
+    let file_content = std::fs::read_to_string(path).expect("Failed to read file");
+    serde_json::from_str(&file_content).expect("Failed to parse JSON")
+}
+
+fn unsupported_modes() -> BTreeSet<String> {
+    read_json::<Vec<UnsupportedMode>>(UNSUPPORTED_MODES)
+        .into_iter()
+        .inspect(|mode| assert!(!mode.reason.is_empty(), "{} has no reason", mode.mode))
+        .map(|mode| mode.mode)
+        .collect()
+}
+
+/// Checks that every case of 'path' that isn't supported is of a mode listed as unsupported.
+fn assert_unsupported_cases_are_listed(cases: &[KatCase], path: &str) {
+    let unsupported = unsupported_modes();
+    for (i, case) in cases.iter().enumerate().filter(|(_, case)| !case.is_supported()) {
+        let unlisted: Vec<_> = case.modes().into_iter().filter(|mode| mode != "blake2b").collect();
+        assert!(
+            unlisted.iter().any(|mode| unsupported.contains(mode)),
+            "Case {i} of {path} needs {unlisted:?}, which is neither supported nor listed in \
+             {UNSUPPORTED_MODES}"
+        );
+    }
+}
+
+#[test]
+fn test_test_vector_is_the_complete_blake2b_section() {
+    let cases: Vec<KatCase> = read_json(TEST_VECTOR);
+    let key: String = (0..64u8).map(|byte| format!("{byte:02x}")).collect();
+    let expected_inputs: Vec<String> =
+        (0..256).map(|len| (0..len).map(|byte| format!("{byte:02x}")).collect()).collect();
+
+    let unkeyed: Vec<_> = cases.iter().filter(|case| case.key.is_empty()).collect();
+    let keyed: Vec<_> = cases.iter().filter(|case| case.key == key).collect();
+    assert_eq!(unkeyed.len() + keyed.len(), cases.len());
+    for cases in [unkeyed, keyed] {
+        let inputs: Vec<_> = cases.iter().map(|case| case.input.clone()).collect();
+        assert_eq!(inputs, expected_inputs);
+        assert!(cases.iter().all(|case| case.hash == "blake2b" && case.out.len() == 128));
+    }
+}
+
+#[test]
+fn test_unsupported_cases_are_listed() {
+    assert_unsupported_cases_are_listed(&read_json::<Vec<KatCase>>(TEST_VECTOR), TEST_VECTOR);
+    assert_unsupported_cases_are_listed(&read_json::<Vec<KatCase>>(EXTENDED_KAT), EXTENDED_KAT);
+}
+
+#[test]
+fn test_extended_kat_in_circuit() {
+    let test_vector: BTreeSet<KatCase> =
+        read_json::<Vec<KatCase>>(TEST_VECTOR).into_iter().collect();
+    let cases: Vec<KatCase> = read_json(EXTENDED_KAT);
+    let new_cases: Vec<_> =
+        cases.iter().filter(|case| case.is_supported() && !test_vector.contains(*case)).collect();
+
+    assert!(!new_cases.is_empty());
+    for case in new_cases {
+        run_test(&case.input, &case.key, &case.out);
+    }
+}
+
+#[test]
+fn test_unsupported_modes_are_not_run() {
+    for mode in unsupported_modes() {
+        let case = KatCase {
+            hash: mode.split('+').next().unwrap().to_string(),
+            input: String::new(),
+            key: String::new(),
+            salt: mode.ends_with("+salt").then(String::new),
+            personal: mode.ends_with("+personal").then(String::new),
+            out: String::new(),
+        };
+        assert!(!case.is_supported(), "{mode} is listed as unsupported but would be run");
+    }
+}
@@ -0,0 +1,78 @@
+use crate::blake2b::blake2b_chip::Blake2bChip;
+use crate::usage_utils::layout::{circuit_layout, LayoutRow};
+use midnight_curves::bls12_381::Fq;
+
+fn chip_rows(rows: &[LayoutRow]) -> Vec<&LayoutRow> {
+    rows.iter().filter(|row| !row.operation.is_empty()).collect()
+}
+
+#[test]
+fn test_layout_labels_every_row_of_the_region_of_the_chip() {
+    let layout = circuit_layout(300, 17, 32).unwrap();
+    let chip_rows = chip_rows(&layout.rows);
+
+    assert_eq!(chip_rows.len(), Blake2bChip::<Fq>::rows_required(300, 17));
+    assert!(chip_rows.windows(2).all(|rows| rows[1].row == rows[0].row + 1));
+    assert_eq!(chip_rows[0].operation, "iv constants");
+    assert_eq!(chip_rows.last().unwrap().block, Some(3));
+    assert_eq!(chip_rows.last().unwrap().operation, "h[7] = h[7] ^ v[15]");
+    assert!(chip_rows.iter().all(|row| row.regions == ["single region"]));
+}
+
+#[test]
+fn test_layout_of_the_mixes() {
+    let layout = circuit_layout(3, 0, 64).unwrap();
+    let mix_rows: Vec<_> = layout.rows.iter().filter(|row| row.mix.is_some()).collect();
+    assert_eq!(mix_rows.len(), 12 * 8 * 25);
+
+    for mix in mix_rows.chunks(25) {
+        let (round, index) = (mix[0].round, mix[0].mix);
+        assert!(mix.iter().all(|row| row.round == round && row.mix == index));
+        // The first addition copies both operands, so its gate is enabled on its first row.
+        assert_eq!(mix[0].operation, "a = a + b");
+        assert!(mix[0].selectors.contains(&"q_add"));
+        // The rotation by 63 is the only one with its own gate.
+        assert_eq!(mix[24].operation, "b = b >>> 63");
+        assert!(mix[24].selectors.contains(&"q_rot63"));
+        assert_eq!(mix.iter().filter(|row| row.selectors.contains(&"q_rot63")).count(), 1);
+    }
+}
+
+#[test]
+fn test_operations_reusing_the_previous_result_enable_their_gate_on_its_row() {
+    let layout = circuit_layout(3, 0, 64).unwrap();
+    let mix_rows: Vec<_> = layout.rows.iter().filter(|row| row.mix.is_some()).collect();
+
+    for rows in mix_rows.windows(2) {
+        let (previous, row) = (rows[0], rows[1]);
+        if previous.operation == row.operation || previous.mix != row.mix {
+            continue;
+        }
+        if row.operation.contains('+') {
+            assert!(previous.selectors.contains(&"q_add"), "{previous:?}");
+        }
+        if row.operation.contains('^') {
+            assert!(previous.selectors.contains(&"q_xor"), "{previous:?}");
+        }
+    }
+}
+
+#[test]
+fn test_negation_is_only_in_the_last_block() {
+    let layout = circuit_layout(200, 0, 64).unwrap();
+    let negate_rows: Vec<_> =
+        layout.rows.iter().filter(|row| row.selectors.contains(&"q_negate")).collect();
+
+    assert_eq!(negate_rows.len(), 1);
+    assert_eq!(negate_rows[0].block, Some(1));
+    assert_eq!(negate_rows[0].operation, "v[14] = !v[14]");
+}
+
+#[test]
+fn test_layout_table_has_a_line_per_row() {
+    let layout = circuit_layout(0, 0, 64).unwrap();
+    let table = layout.to_table();
+
+    assert_eq!(table.lines().count(), layout.rows.len() + 1);
+    assert!(table.lines().nth(1).unwrap().contains("iv constants"));
+}
@@ -5,3 +5,33 @@ mod vector_tests;
 mod variable_output_length_tests;
 mod variable_key_length_tests;
 mod circuit_in_production;
+mod witness_tests;
+mod chip_test_circuit;
+mod shared_columns_tests;
+mod range_check_table_tests;
+mod serialization_tests;
+mod rows_required_tests;
+mod cost_report_tests;
+mod generic_field_tests;
+mod committed_instances_tests;
+mod public_input_tests;
+mod precomputed_prefix_tests;
+mod kat_tests;
+mod recorded_layout;
+mod tampering_tests;
+mod constraint_coverage_tests;
+mod property_tests;
+mod vk_fingerprint_tests;
+mod layout_tests;
+mod diagnostics_tests;
+mod real_prover_tests;
+mod proof_artifacts_tests;
+mod batch_verification_tests;
+mod multi_circuit_proof_tests;
+mod compression_gadget_tests;
+
+/// The digest of 'input' computed by blake2b_simd, as the field elements of the public output.
+fn expected_output_for(input: &[u8], output_size: usize) -> Vec<Fq> {
+    let digest = blake2b_simd::Params::new().hash_length(output_size).hash(input);
+    digest.as_bytes().iter().map(|byte| Fq::from(*byte as u64)).collect()
+}
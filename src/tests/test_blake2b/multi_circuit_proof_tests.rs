@@ -0,0 +1,67 @@
+use super::*;
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+
+/// Messages of the same length, so that their circuits share the keys.
+const MESSAGES: [&[u8]; 3] = [b"first message", b"other message", b"third message"];
+
+fn circuit_for(message: &[u8]) -> Blake2bCircuit<Fq> {
+    let input_values = message.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect();
+    Bls12CircuitRunner::create_circuit_for_inputs(input_values, message.len(), vec![], 0, 64)
+        .unwrap()
+}
+
+#[test]
+fn test_single_proof_of_many_circuits_verifies_their_digests() {
+    let circuits: Vec<Blake2bCircuit<Fq>> = MESSAGES.iter().map(|m| circuit_for(m)).collect();
+    let params = Bls12CircuitRunner::create_params(circuits[0].k());
+    let vk = Bls12CircuitRunner::create_vk(&circuits[0], &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuits[0], vk.clone()).unwrap();
+
+    let digests: Vec<Vec<Fq>> = MESSAGES.iter().map(|m| expected_output_for(m, 64)).collect();
+    let instances: Vec<[&[Fq]; 1]> = digests.iter().map(|digest| [digest.as_slice()]).collect();
+    let instances: Vec<&[&[Fq]]> = instances.iter().map(|columns| columns.as_slice()).collect();
+    let artifacts =
+        Bls12CircuitRunner::create_proof_for_circuits(&instances, circuits, &params, &pk).unwrap();
+    assert_eq!(artifacts.stats.public_input_elements, MESSAGES.len() * 64);
+
+    let no_commitments = [&[][..]; MESSAGES.len()];
+    Bls12CircuitRunner::verify_for_circuits(
+        &vk,
+        &params,
+        &no_commitments,
+        &instances,
+        &artifacts.proof,
+    )
+    .unwrap();
+
+    let mut swapped_instances = instances.clone();
+    swapped_instances.swap(0, 2);
+    assert!(Bls12CircuitRunner::verify_for_circuits(
+        &vk,
+        &params,
+        &no_commitments,
+        &swapped_instances,
+        &artifacts.proof,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_circuits_of_different_shapes_are_not_proven_together() {
+    let circuit = circuit_for(MESSAGES[0]);
+    let params = Bls12CircuitRunner::create_params(circuit.k());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk).unwrap();
+
+    let longer_message = b"a longer message";
+    let digests = [expected_output_for(MESSAGES[0], 64), expected_output_for(longer_message, 64)];
+    let result = Bls12CircuitRunner::create_proof_for_circuits(
+        &[&[&digests[0]], &[&digests[1]]],
+        vec![circuit, circuit_for(longer_message)],
+        &params,
+        &pk,
+    );
+    assert!(matches!(result, Err(Blake2bError::MismatchedCircuitShape(1))));
+}
@@ -0,0 +1,162 @@
+use super::*;
+use crate::blake2b::blake2b_chip::{Blake2bChip, Blake2bConfig};
+use crate::error::Blake2bError;
+use midnight_proofs::circuit::SimpleFloorPlanner;
+use midnight_proofs::dev::cost_model::circuit_model;
+use midnight_proofs::dev::MockProver;
+use midnight_proofs::plonk::{Circuit, Instance};
+use std::array;
+
+/// A circuit that proves the hash of a public prefix followed by a private suffix, where the
+/// compressions of the prefix are computed outside the circuit.
+struct PrecomputedPrefixCircuit {
+    prefix_state: [u64; 8],
+    prefix_len: usize,
+    suffix: Vec<u8>,
+    output_size: usize,
+}
+
+impl PrecomputedPrefixCircuit {
+    fn new(prefix: &[u8], key: &[u8], suffix: &[u8], output_size: usize) -> Self {
+        Self {
+            prefix_state: Blake2bChip::<Fq>::precompute_prefix_state(prefix, key, output_size)
+                .unwrap(),
+            prefix_len: prefix.len() + if key.is_empty() { 0 } else { 128 },
+            suffix: suffix.to_vec(),
+            output_size,
+        }
+    }
+}
+
+impl Circuit<Fq> for PrecomputedPrefixCircuit {
+    type Config = (Blake2bConfig, Column<Instance>);
+    type Params = ();
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            prefix_state: self.prefix_state,
+            prefix_len: self.prefix_len,
+            suffix: self.suffix.clone(),
+            output_size: self.output_size,
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let full_number_u64 = meta.advice_column();
+        let limbs: [Column<Advice>; 8] = array::from_fn(|_| meta.advice_column());
+        let constants = meta.fixed_column();
+        let instance = meta.instance_column();
+        meta.enable_equality(instance);
+        (Blake2bChip::configure(meta, constants, full_number_u64, limbs), instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let suffix = layouter.assign_region(
+            || "Suffix",
+            |mut region| {
+                self.suffix
+                    .iter()
+                    .enumerate()
+                    .map(|(index, byte)| {
+                        region.assign_advice(
+                            || "suffix byte",
+                            config.0.limbs[index % 8],
+                            index / 8,
+                            || Value::known(Fq::from(*byte as u64)),
+                        )
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let chip = Blake2bChip::new(&config.0);
+        chip.load(&mut layouter)?;
+        let result = chip.hash_with_precomputed_prefix(
+            &mut layouter,
+            self.prefix_state,
+            self.prefix_len,
+            &suffix,
+        )?;
+        for (i, byte) in result.iter().enumerate().take(self.output_size) {
+            layouter.constrain_instance(byte.cell(), config.1, i)?;
+        }
+        Ok(())
+    }
+}
+
+fn expected_keyed_output_for(input: &[u8], key: &[u8], output_size: usize) -> Vec<Fq> {
+    let digest = blake2b_simd::Params::new().hash_length(output_size).key(key).hash(input);
+    digest.as_bytes().iter().map(|byte| Fq::from(*byte as u64)).collect()
+}
+
+fn prefix_of_blocks(blocks: usize) -> Vec<u8> {
+    (0..blocks * 128).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn test_hash_with_precomputed_prefix() {
+    let prefix = prefix_of_blocks(2);
+    let suffix = b"private suffix";
+    let circuit = PrecomputedPrefixCircuit::new(&prefix, &[], suffix, 64);
+
+    let expected_output = expected_output_for(&[prefix.as_slice(), suffix].concat(), 64);
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    prover.verify().unwrap();
+}
+
+#[test]
+fn test_hash_with_precomputed_keyed_prefix_and_multi_block_suffix() {
+    let prefix = prefix_of_blocks(1);
+    let key = b"a public key";
+    let suffix: Vec<u8> = (0..300).map(|i| (i * 7 % 256) as u8).collect();
+    let circuit = PrecomputedPrefixCircuit::new(&prefix, key, &suffix, 32);
+
+    let expected_output = expected_keyed_output_for(&[prefix, suffix].concat(), key, 32);
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    prover.verify().unwrap();
+}
+
+#[test]
+fn test_hash_with_precomputed_prefix_uses_the_prefix_state() {
+    let suffix = b"private suffix";
+    let circuit = PrecomputedPrefixCircuit::new(&prefix_of_blocks(2), &[], suffix, 64);
+
+    let mut other_prefix = prefix_of_blocks(2);
+    other_prefix[0] ^= 1;
+    let expected_output = expected_output_for(&[other_prefix.as_slice(), suffix].concat(), 64);
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_hash_with_precomputed_prefix_rows() {
+    let suffix = vec![0u8; 200];
+    let circuit = PrecomputedPrefixCircuit::new(&prefix_of_blocks(1), &[], &suffix, 64);
+    let model = circuit_model::<Fq, 48, 32>(&circuit);
+    assert_eq!(
+        model.rows,
+        suffix.len().div_ceil(8)
+            + Blake2bChip::<Fq>::rows_required_with_precomputed_prefix(suffix.len())
+    );
+}
+
+#[test]
+fn test_prefix_of_partial_blocks_is_rejected() {
+    let result = Blake2bChip::<Fq>::precompute_prefix_state(&[0; 100], &[], 64);
+    assert!(matches!(result, Err(Blake2bError::InvalidPrefixLength(100))));
+}
+
+#[test]
+fn test_empty_suffix_is_rejected() {
+    let circuit = PrecomputedPrefixCircuit::new(&prefix_of_blocks(1), &[], &[], 64);
+    let result = MockProver::run(&circuit, vec![vec![]]);
+    assert!(matches!(
+        result,
+        Err(Error::Synthesis(message)) if message == Blake2bError::EmptySuffix.to_string()
+    ));
+}
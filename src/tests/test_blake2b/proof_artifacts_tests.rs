@@ -0,0 +1,85 @@
+use super::*;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, DigestLayout, PublicInputBytes};
+use crate::usage_utils::circuit_runner::{Bls12CircuitRunner, ProofArtifacts};
+use ff::PrimeField;
+
+fn values_for(bytes: &[u8]) -> Vec<Value<Fq>> {
+    bytes.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect()
+}
+
+fn packed_circuit_for(message: &[u8], output_size: usize) -> Blake2bCircuit<Fq> {
+    Bls12CircuitRunner::create_circuit_for_inputs(
+        values_for(message),
+        message.len(),
+        vec![],
+        0,
+        output_size,
+    )
+    .unwrap()
+    .with_digest_layout(DigestLayout::PackedWords)
+}
+
+fn digest_of(message: &[u8], output_size: usize) -> Vec<u8> {
+    blake2b_simd::Params::new().hash_length(output_size).hash(message).as_bytes().to_vec()
+}
+
+fn mock_prover_accepts(circuit: &Blake2bCircuit<Fq>, instance: Vec<Fq>) -> bool {
+    let prover = Bls12CircuitRunner::mock_prove_with_instances(vec![instance], circuit).unwrap();
+    prover.verify().is_ok()
+}
+
+#[test]
+fn test_packed_digest_is_constrained_to_its_words() {
+    let message = b"a message whose digest is packed into words";
+    let circuit = packed_circuit_for(message, 64);
+    let digest = digest_of(message, 64);
+
+    let instance: Vec<Fq> = DigestLayout::PackedWords.instances_for(&digest);
+    assert_eq!(instance.len(), 8);
+    assert!(mock_prover_accepts(&circuit, instance.clone()));
+
+    let mut wrong_instance = instance;
+    wrong_instance[3] += Fq::from(1 << 40);
+    assert!(!mock_prover_accepts(&circuit, wrong_instance));
+
+    let byte_instance: Vec<Fq> = DigestLayout::Bytes.instances_for(&digest);
+    assert!(!mock_prover_accepts(&circuit, byte_instance));
+}
+
+#[test]
+fn test_packed_digest_of_odd_output_size_is_zero_padded() {
+    let message = b"an odd output size";
+    let digest = digest_of(message, 20);
+    let instance: Vec<Fq> = DigestLayout::PackedWords.instances_for(&digest);
+    assert_eq!(instance.len(), 3);
+    assert_eq!(instance[2], Fq::from(u32::from_le_bytes(digest[16..].try_into().unwrap()) as u64));
+
+    assert!(mock_prover_accepts(&packed_circuit_for(message, 20), instance));
+}
+
+#[test]
+fn test_public_input_bytes_follow_the_packed_digest() {
+    let message = b"public header, private suffix";
+    let circuit = packed_circuit_for(message, 32)
+        .with_public_input(vec![PublicInputBytes::Instance(0..13)])
+        .unwrap();
+    let mut instance: Vec<Fq> = DigestLayout::PackedWords.instances_for(&digest_of(message, 32));
+    instance.extend(DigestLayout::Bytes.instances_for::<Fq>(&message[..13]));
+
+    assert!(mock_prover_accepts(&circuit, instance));
+}
+
+#[test]
+fn test_proof_artifacts_account_for_every_public_input() {
+    let digest = [Fq::from(1), Fq::from(2), Fq::from(3)];
+    let header = [Fq::from(4), Fq::from(5)];
+    let artifacts = ProofArtifacts::new(vec![0u8; 100], &[&digest, &header]);
+
+    let element_size = Fq::from(0).to_repr().as_ref().len();
+    assert_eq!(artifacts.stats.proof_bytes, 100);
+    assert_eq!(artifacts.stats.public_input_elements, 5);
+    assert_eq!(artifacts.stats.public_input_bytes, 5 * element_size);
+    assert_eq!(artifacts.stats.total_bytes(), 100 + 5 * element_size);
+    assert_eq!(artifacts.public_inputs.len(), artifacts.stats.public_input_bytes);
+    assert_eq!(artifacts.public_inputs[4 * element_size..][..element_size], Fq::from(5).to_repr());
+}
@@ -0,0 +1,61 @@
+//! Differential tests against blake2b_simd for arbitrary message, key and output sizes. A failing
+//! case is shrunk to a minimal one and persisted under 'proptest-regressions/', where it is run
+//! again before any new case. Those files should be committed as regression vectors.
+
+use super::*;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::test_runner::FileFailurePersistence;
+
+/// The largest message, in blocks.
+const MAX_MESSAGE_BLOCKS: usize = 3;
+
+/// Messages from 0 to [MAX_MESSAGE_BLOCKS] blocks, drawing exact multiples of the block size as
+/// often as any other length.
+fn message() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        vec(any::<u8>(), 0..=MAX_MESSAGE_BLOCKS * 128),
+        (0..=MAX_MESSAGE_BLOCKS).prop_flat_map(|blocks| vec(any::<u8>(), blocks * 128)),
+    ]
+}
+
+fn values_for(bytes: &[u8]) -> Vec<Value<Fq>> {
+    bytes.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect()
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 32,
+        failure_persistence: Some(Box::new(FileFailurePersistence::SourceParallel(
+            "proptest-regressions",
+        ))),
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn test_circuit_agrees_with_blake2b_simd(
+        message in message(),
+        key in vec(any::<u8>(), 0..=64),
+        output_size in 1..=64usize,
+    ) {
+        let digest =
+            blake2b_simd::Params::new().hash_length(output_size).key(&key).hash(&message);
+        let expected_output: Vec<Fq> =
+            digest.as_bytes().iter().map(|byte| Fq::from(*byte as u64)).collect();
+
+        let circuit = Bls12CircuitRunner::create_circuit_for_inputs(
+            values_for(&message),
+            message.len(),
+            values_for(&key),
+            key.len(),
+            output_size,
+        )
+        .unwrap();
+        let prover =
+            Bls12CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output, &circuit)
+                .unwrap();
+        let result = prover.verify();
+        prop_assert!(result.is_ok(), "{:?}", result.unwrap_err());
+    }
+}
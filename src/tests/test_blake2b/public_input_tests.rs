@@ -0,0 +1,101 @@
+use super::*;
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, PublicInputBytes};
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+
+const HEADER: &[u8] = b"protocol header v1";
+
+fn message_with_header(header: &[u8]) -> Vec<u8> {
+    [header, b" and a private suffix"].concat()
+}
+
+fn values_for(bytes: &[u8]) -> Vec<Value<Fq>> {
+    bytes.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect()
+}
+
+fn circuit_for(message: &[u8], public_input: Vec<PublicInputBytes>) -> Blake2bCircuit<Fq> {
+    Bls12CircuitRunner::create_circuit_with_public_input(
+        values_for(message),
+        message.len(),
+        vec![],
+        0,
+        64,
+        public_input,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_public_prefix_is_constrained_to_the_instance() {
+    let message = message_with_header(HEADER);
+    let circuit = circuit_for(&message, vec![PublicInputBytes::Instance(0..HEADER.len())]);
+    let digest = expected_output_for(&message, 64);
+
+    let instance = Bls12CircuitRunner::public_instance_for(&digest, HEADER);
+    let prover = Bls12CircuitRunner::mock_prove_with_instances(vec![instance], &circuit).unwrap();
+    assert!(prover.verify().is_ok());
+
+    let instance = Bls12CircuitRunner::public_instance_for(&digest, b"protocol header v2");
+    let prover = Bls12CircuitRunner::mock_prove_with_instances(vec![instance], &circuit).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_public_ranges_follow_the_digest_in_order() {
+    let message = message_with_header(HEADER);
+    let circuit = circuit_for(
+        &message,
+        vec![PublicInputBytes::Instance(9..15), PublicInputBytes::Instance(0..8)],
+    );
+    let digest = expected_output_for(&message, 64);
+
+    let public_bytes = [&message[9..15], &message[0..8]].concat();
+    let instance = Bls12CircuitRunner::public_instance_for(&digest, &public_bytes);
+    let prover = Bls12CircuitRunner::mock_prove_with_instances(vec![instance], &circuit).unwrap();
+    assert!(prover.verify().is_ok());
+}
+
+#[test]
+fn test_fixed_prefix_is_constrained_to_the_fixed_bytes() {
+    let fixed = vec![PublicInputBytes::Fixed {
+        offset: 0,
+        bytes: HEADER.to_vec(),
+    }];
+
+    let message = message_with_header(HEADER);
+    let prover = Bls12CircuitRunner::mock_prove_with_public_inputs_ref(
+        &expected_output_for(&message, 64),
+        &circuit_for(&message, fixed.clone()),
+    )
+    .unwrap();
+    assert!(prover.verify().is_ok());
+
+    let message = message_with_header(b"protocol header v2");
+    let prover = Bls12CircuitRunner::mock_prove_with_public_inputs_ref(
+        &expected_output_for(&message, 64),
+        &circuit_for(&message, fixed),
+    )
+    .unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_public_range_outside_the_input_is_rejected() {
+    let message = message_with_header(HEADER);
+    let result = Bls12CircuitRunner::create_circuit_with_public_input(
+        values_for(&message),
+        message.len(),
+        vec![],
+        0,
+        64,
+        vec![PublicInputBytes::Fixed {
+            offset: message.len() - 2,
+            bytes: vec![0; 3],
+        }],
+    );
+    let end = message.len() + 1;
+    assert!(matches!(
+        result,
+        Err(Blake2bError::InvalidPublicInputRange(range)) if range == (end - 3..end)
+    ));
+}
@@ -0,0 +1,32 @@
+use super::chip_test_circuit::{ChipColumns, ChipTestCircuit};
+use super::*;
+use crate::blake2b::blake2b_chip::RangeCheckTable;
+use midnight_proofs::dev::MockProver;
+use midnight_proofs::plonk::Circuit;
+
+#[test]
+fn test_blake2b_with_xor_range_check_table() {
+    let input = b"range checks against the xor table".to_vec();
+    let expected_output = expected_output_for(&input, 64);
+    let circuit = ChipTestCircuit {
+        input,
+        output_size: 64,
+        columns: ChipColumns::Owned(RangeCheckTable::Xor),
+    };
+
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    prover.verify().unwrap();
+}
+
+#[test]
+fn test_xor_range_check_table_saves_one_table_column() {
+    let mut dedicated = ConstraintSystem::<Fq>::default();
+    ChipTestCircuit::configure_with_params(
+        &mut dedicated,
+        ChipColumns::Owned(RangeCheckTable::Dedicated),
+    );
+    let mut xor = ConstraintSystem::<Fq>::default();
+    ChipTestCircuit::configure_with_params(&mut xor, ChipColumns::Owned(RangeCheckTable::Xor));
+
+    assert_eq!(xor.num_fixed_columns() + 1, dedicated.num_fixed_columns());
+}
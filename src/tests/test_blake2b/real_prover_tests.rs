@@ -0,0 +1,62 @@
+//! Real prove and verify of multi-block keyed hashes, with the parameters of the smallest k every
+//! circuit fits in. They take minutes, so they're ignored by default:
+//! `cargo test --release real_prover_tests -- --ignored`.
+
+use crate::error::Blake2bError;
+use crate::usage_utils::circuit_runner::{Bls12CircuitRunner, ParamsCache};
+use midnight_proofs::plonk::Error;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// (input size, key size, output size) of the proven hashes.
+const SIZES: [(usize, usize, usize); 4] =
+    [(129, 1, 64), (256, 64, 64), (300, 17, 32), (1000, 32, 64)];
+
+/// Random input and key of the given sizes, as hex strings, along with the hex digest.
+fn keyed_hash_case(input_size: usize, key_size: usize, output_size: usize) -> [String; 3] {
+    let mut rng = ChaCha8Rng::seed_from_u64((input_size * 100 + key_size) as u64);
+    let input: Vec<u8> = (0..input_size).map(|_| rng.gen()).collect();
+    let key: Vec<u8> = (0..key_size).map(|_| rng.gen()).collect();
+    let digest = blake2b_simd::Params::new().hash_length(output_size).key(&key).hash(&input);
+    [hex::encode(input), hex::encode(key), hex::encode(digest.as_bytes())]
+}
+
+#[test]
+#[ignore]
+fn test_real_prover_on_multi_block_keyed_inputs() {
+    let mut params_cache = ParamsCache::new();
+    for (input_size, key_size, output_size) in SIZES {
+        let [input, key, out] = keyed_hash_case(input_size, key_size, output_size);
+        let artifacts =
+            Bls12CircuitRunner::real_preprocess_inputs_synthesize_prove_and_verify_with_params(
+                input,
+                out,
+                key,
+                &mut params_cache,
+            )
+            .unwrap_or_else(|error| {
+                panic!("{input_size} bytes with a {key_size}-byte key: {error}")
+            });
+        assert_eq!(artifacts.stats.proof_bytes, artifacts.proof.len());
+        assert!(artifacts.stats.proof_bytes > 0, "{output_size}-byte output: empty proof");
+    }
+}
+
+#[test]
+#[ignore]
+fn test_real_prover_rejects_wrong_digest_of_multi_block_keyed_input() {
+    let [input, key, out] = keyed_hash_case(300, 17, 32);
+    let mut wrong_out = hex::decode(out).unwrap();
+    wrong_out[31] ^= 1;
+
+    let result = Bls12CircuitRunner::real_preprocess_inputs_synthesize_prove_and_verify_with_params(
+        input,
+        hex::encode(wrong_out),
+        key,
+        &mut ParamsCache::new(),
+    );
+    assert!(
+        matches!(result, Err(Blake2bError::Synthesis(Error::Opening))),
+        "The proof of a wrong digest should be rejected by the verifier, got {result:?}"
+    );
+}
@@ -0,0 +1,313 @@
+//! A floor planner for soundness analyses of the full chip. It lays out a [Blake2bCircuit] like
+//! the [SimpleFloorPlanner] while recording the advice cells, enabled selectors and copy
+//! constraints of the synthesis, and it can change the values of chosen advice cells, as a
+//! [Mutation], before they reach the [MockProver].
+
+use super::*;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, Blake2bInstanceMode};
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use midnight_proofs::circuit::{Layouter, SimpleFloorPlanner};
+use midnight_proofs::dev::{MockProver, VerifyFailure};
+use midnight_proofs::plonk::{
+    Advice, Any, Assignment, Challenge, Circuit, Column, ConstraintSystem, Error, Fixed,
+    FloorPlanner, Instance, Selector,
+};
+use midnight_proofs::utils::rational::Rational;
+use std::any::Any as AnyType;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+pub(super) use crate::usage_utils::layout::{AdviceCell, AnyCell};
+
+/// What the chip assigned during a synthesis, as seen by the floor planner.
+#[derive(Clone, Debug, Default)]
+pub(super) struct RecordedLayout {
+    /// Every advice cell, in assignment order.
+    pub(super) advice_cells: Vec<AdviceCell>,
+    /// The (selector index, row) of every enabled selector.
+    pub(super) enabled_selectors: Vec<(usize, usize)>,
+    /// Every copy constraint, between two cells of any kind of column.
+    pub(super) copies: Vec<(AnyCell, AnyCell)>,
+    /// The value of every advice cell, by (column, row), before any mutation.
+    pub(super) values: HashMap<(usize, usize), Fq>,
+}
+
+impl RecordedLayout {
+    /// The value 'cell' was assigned.
+    pub(super) fn value_of(&self, cell: &AdviceCell) -> Fq {
+        self.values[&(cell.column, cell.row)]
+    }
+}
+
+/// A change to the values of some advice cells, as the amounts added to them. Adding one to a
+/// cell breaks any gate that determines it, while a mutation of many cells can preserve the gates
+/// and leave the lookups as the only constraints that reject it.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Mutation(Vec<(AdviceCell, Fq)>);
+
+impl Mutation {
+    /// Adds one to 'cell'.
+    pub(super) fn add_one(cell: &AdviceCell) -> Self {
+        Self::default().add(cell, Fq::ONE)
+    }
+
+    /// Replaces the value x of 'cell' with p - x, i.e. with -x.
+    pub(super) fn negate(cell: &AdviceCell, layout: &RecordedLayout) -> Self {
+        Self::default().add(cell, -layout.value_of(cell).double())
+    }
+
+    /// Also adds 'delta' to 'cell'.
+    pub(super) fn add(mut self, cell: &AdviceCell, delta: Fq) -> Self {
+        self.0.push((cell.clone(), delta));
+        self
+    }
+}
+
+thread_local! {
+    /// The layout of the last synthesis.
+    static LAYOUT: RefCell<RecordedLayout> = RefCell::new(RecordedLayout::default());
+    /// The amount added to the advice cells mutated during synthesis, by (column, row).
+    static TAMPERED_CELLS: RefCell<HashMap<(usize, usize), Fq>> = RefCell::new(HashMap::new());
+}
+
+/// A [Blake2bCircuit] that is laid out by the [RecordingFloorPlanner].
+pub(super) struct RecordedCircuit(pub(super) Blake2bCircuit<Fq>);
+
+impl Circuit<Fq> for RecordedCircuit {
+    type Config = <Blake2bCircuit<Fq> as Circuit<Fq>>::Config;
+    type Params = Blake2bInstanceMode;
+    type FloorPlanner = RecordingFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self(self.0.without_witnesses())
+    }
+
+    fn params(&self) -> Self::Params {
+        self.0.params()
+    }
+
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<Fq>,
+        instance_mode: Self::Params,
+    ) -> Self::Config {
+        Blake2bCircuit::configure_with_params(meta, instance_mode)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        Blake2bCircuit::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fq>) -> Result<(), Error> {
+        self.0.synthesize(config, layouter)
+    }
+}
+
+/// Lays out the circuit like the [SimpleFloorPlanner], but through a [RecordingAssignment].
+pub(super) struct RecordingFloorPlanner;
+
+impl FloorPlanner for RecordingFloorPlanner {
+    fn synthesize<F: Field, CS: Assignment<F>, C: Circuit<F>>(
+        cs: &mut CS,
+        circuit: &C,
+        config: C::Config,
+        constants: Vec<Column<Fixed>>,
+    ) -> Result<(), Error> {
+        LAYOUT.with(|layout| *layout.borrow_mut() = RecordedLayout::default());
+        let mut assignment = RecordingAssignment {
+            cs,
+            region: String::new(),
+        };
+        SimpleFloorPlanner::synthesize(&mut assignment, circuit, config, constants)
+    }
+}
+
+/// Forwards every call to the wrapped [Assignment], recording the layout in [LAYOUT] and adding
+/// their amounts to the [TAMPERED_CELLS].
+struct RecordingAssignment<'a, CS> {
+    cs: &'a mut CS,
+    region: String,
+}
+
+impl<F: Field, CS: Assignment<F>> Assignment<F> for RecordingAssignment<'_, CS> {
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        let name: String = name_fn().into();
+        self.region = name.clone();
+        self.cs.enter_region(|| name)
+    }
+
+    fn annotate_column<A, AR>(&mut self, annotation: A, column: Column<Any>)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.cs.annotate_column(annotation, column)
+    }
+
+    fn exit_region(&mut self) {
+        self.cs.exit_region()
+    }
+
+    fn enable_selector<A, AR>(
+        &mut self,
+        annotation: A,
+        selector: &Selector,
+        row: usize,
+    ) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        LAYOUT.with(|layout| layout.borrow_mut().enabled_selectors.push((selector.index(), row)));
+        self.cs.enable_selector(annotation, selector, row)
+    }
+
+    fn query_instance(&self, column: Column<Instance>, row: usize) -> Result<Value<F>, Error> {
+        self.cs.query_instance(column, row)
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Rational<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        let annotation: String = annotation().into();
+        LAYOUT.with(|layout| {
+            layout.borrow_mut().advice_cells.push(AdviceCell {
+                region: self.region.clone(),
+                annotation: annotation.clone(),
+                column: column.index(),
+                row,
+            })
+        });
+        let delta =
+            TAMPERED_CELLS.with(|cells| cells.borrow().get(&(column.index(), row)).copied());
+        let value = to().map(|value| {
+            let value: Rational<F> = value.into();
+            if let Some(honest) = as_fq(value.evaluate()) {
+                LAYOUT.with(|layout| {
+                    layout.borrow_mut().values.insert((column.index(), row), honest)
+                });
+            }
+            match delta {
+                Some(delta) => value + from_fq::<F>(delta),
+                None => value,
+            }
+        });
+        self.cs.assign_advice(|| annotation, column, row, || value)
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Fixed>,
+        row: usize,
+        to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Rational<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.cs.assign_fixed(annotation, column, row, to)
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        LAYOUT.with(|layout| {
+            layout.borrow_mut().copies.push(((left_column, left_row), (right_column, right_row)))
+        });
+        self.cs.copy(left_column, left_row, right_column, right_row)
+    }
+
+    fn fill_from_row(
+        &mut self,
+        column: Column<Fixed>,
+        row: usize,
+        to: Value<Rational<F>>,
+    ) -> Result<(), Error> {
+        self.cs.fill_from_row(column, row, to)
+    }
+
+    fn get_challenge(&self, challenge: Challenge) -> Value<F> {
+        self.cs.get_challenge(challenge)
+    }
+
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.cs.push_namespace(name_fn)
+    }
+
+    fn pop_namespace(&mut self, gadget_name: Option<String>) {
+        self.cs.pop_namespace(gadget_name)
+    }
+}
+
+/// 'value' as an [Fq], which it is in the [RecordedCircuit].
+fn as_fq<F: Field>(value: F) -> Option<Fq> {
+    (&value as &dyn AnyType).downcast_ref::<Fq>().copied()
+}
+
+fn from_fq<F: Field>(value: Fq) -> F {
+    *(&value as &dyn AnyType).downcast_ref::<F>().expect("Only circuits over Fq are tampered with")
+}
+
+/// The hash of "abc" with a 64-byte digest, which fits in a single block with 125 padding bytes.
+pub(super) fn abc_circuit_and_instance() -> (RecordedCircuit, Vec<Fq>) {
+    let input = b"abc";
+    let input_values = input.iter().map(|byte| Value::known(Fq::from(*byte as u64))).collect();
+    let circuit =
+        Bls12CircuitRunner::create_circuit_for_inputs(input_values, input.len(), vec![], 0, 64)
+            .unwrap();
+    (RecordedCircuit(circuit), expected_output_for(input, 64))
+}
+
+/// Synthesizes the circuit with 'mutation' and tells whether the [MockProver] accepts the result.
+/// The cells assigned during synthesis are left in [LAYOUT].
+pub(super) fn mock_prover_accepts(
+    circuit: &RecordedCircuit,
+    instance: &[Fq],
+    mutation: &Mutation,
+) -> bool {
+    mock_prover_failures(circuit, instance, mutation).is_empty()
+}
+
+/// Synthesizes the circuit with 'mutation' and returns the failures of the [MockProver].
+pub(super) fn mock_prover_failures(
+    circuit: &RecordedCircuit,
+    instance: &[Fq],
+    mutation: &Mutation,
+) -> Vec<VerifyFailure> {
+    let deltas = mutation.0.iter().map(|(cell, delta)| ((cell.column, cell.row), *delta));
+    TAMPERED_CELLS.with(|cells| *cells.borrow_mut() = deltas.collect());
+    let prover = MockProver::run(circuit, vec![instance.to_vec()]).unwrap();
+    TAMPERED_CELLS.with(|cells| cells.borrow_mut().clear());
+    prover.verify().err().unwrap_or_default()
+}
+
+/// The layout of an honest synthesis of the circuit.
+pub(super) fn recorded_layout(circuit: &RecordedCircuit, instance: &[Fq]) -> RecordedLayout {
+    let honest = Mutation::default();
+    assert!(mock_prover_accepts(circuit, instance, &honest), "the honest witness must verify");
+    LAYOUT.with(|layout| layout.borrow().clone())
+}
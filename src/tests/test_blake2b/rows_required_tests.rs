@@ -0,0 +1,39 @@
+use crate::blake2b::blake2b_chip::Blake2bChip;
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use midnight_curves::bls12_381::Fq;
+use midnight_proofs::dev::cost_model::circuit_model;
+use midnight_proofs::plonk::k_from_circuit;
+
+/// Input and key sizes covering an empty hash, partial and full blocks, keyed hashes and a
+/// circuit too big for 2^17 rows.
+const SIZES: [(usize, usize); 9] =
+    [(0, 0), (1, 0), (128, 0), (129, 0), (0, 1), (1, 64), (300, 17), (1000, 0), (6912, 0)];
+
+fn circuit_for(input_size: usize, key_size: usize) -> Blake2bCircuit<Fq> {
+    Blake2bCircuit::for_sizes(input_size, key_size, 64).unwrap()
+}
+
+#[test]
+fn test_rows_required_matches_the_layout_of_the_circuit() {
+    for (input_size, key_size) in SIZES {
+        let model = circuit_model::<Fq, 48, 32>(&circuit_for(input_size, key_size));
+        assert_eq!(Blake2bCircuit::<Fq>::rows_required(input_size, key_size), model.rows);
+    }
+}
+
+#[test]
+fn test_k_for_is_the_smallest_k_the_circuit_fits_in() {
+    for (input_size, key_size) in SIZES {
+        let circuit = circuit_for(input_size, key_size);
+        assert_eq!(circuit.k(), k_from_circuit(&circuit));
+    }
+    assert_eq!(Blake2bCircuit::<Fq>::k_for(0, 0), 17);
+    assert_eq!(Blake2bCircuit::<Fq>::k_for(6912, 0), 18);
+}
+
+#[test]
+fn test_rows_required_by_the_chip_for_one_block() {
+    assert_eq!(Blake2bChip::<Fq>::rows_required(0, 0), 2469);
+    assert_eq!(Blake2bChip::<Fq>::rows_required(128, 0), 2469);
+    assert_eq!(Blake2bChip::<Fq>::rows_required(0, 1), 2469);
+}
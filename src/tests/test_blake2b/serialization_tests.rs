@@ -0,0 +1,78 @@
+use crate::usage_utils::blake2b_circuit::Blake2bInstanceMode;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use ff::Field;
+use midnight_curves::bls12_381::{Bls12, Fq};
+use midnight_proofs::poly::kzg::params::ParamsKZG;
+use midnight_proofs::utils::SerdeFormat;
+
+#[test]
+fn test_proof_verifies_with_deserialized_params_and_vk() {
+    let input = String::from("0001");
+    let out = String::from("1c08798dc641aba9dee435e22519a4729a09b2bfe0ff00ef2dcd8ed6f8a07d15eaf4aee52bbf18ab5608a6190f70b90486c8a7d4873710b1115d3debbb4327b5");
+    let circuit_inputs =
+        Bls12CircuitRunner::prepare_parameters_for_test(&input, &String::new(), &out).unwrap();
+    let circuit =
+        Bls12CircuitRunner::create_circuit_for_packed_inputs(circuit_inputs.clone()).unwrap();
+
+    let params = ParamsKZG::<Bls12>::unsafe_setup(17, &mut rand::thread_rng());
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params).unwrap();
+    let pk = Bls12CircuitRunner::create_pk(&circuit, vk.clone()).unwrap();
+    let proof =
+        Bls12CircuitRunner::create_proof(&circuit_inputs.4, circuit, &params, &pk).unwrap().proof;
+
+    let directory = std::env::temp_dir().join(format!("blake2b_halo2_{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+    let params_path = directory.join("params.bin");
+    let vk_path = directory.join("vk.bin");
+    Bls12CircuitRunner::write_params(&params, &params_path, SerdeFormat::RawBytes).unwrap();
+    Bls12CircuitRunner::write_vk(&vk, &vk_path, SerdeFormat::Processed).unwrap();
+    let read_params = Bls12CircuitRunner::read_params(&params_path, SerdeFormat::RawBytes).unwrap();
+    let read_vk =
+        Bls12CircuitRunner::read_vk(&vk_path, SerdeFormat::Processed, Blake2bInstanceMode::Public)
+            .unwrap();
+    std::fs::remove_dir_all(&directory).unwrap();
+
+    Bls12CircuitRunner::verify_with_vk(&read_vk, &read_params, &circuit_inputs.4, &proof).unwrap();
+
+    let vk_bytes = Bls12CircuitRunner::vk_to_bytes(&vk, SerdeFormat::RawBytes);
+    let vk_from_bytes = Bls12CircuitRunner::vk_from_bytes(
+        &vk_bytes,
+        SerdeFormat::RawBytes,
+        Blake2bInstanceMode::Public,
+    )
+    .unwrap();
+    Bls12CircuitRunner::verify_with_vk(&vk_from_bytes, &params, &circuit_inputs.4, &proof).unwrap();
+
+    let mut wrong_digest = circuit_inputs.4;
+    wrong_digest[0] += Fq::ONE;
+    assert!(
+        Bls12CircuitRunner::verify_with_vk(&vk_from_bytes, &params, &wrong_digest, &proof).is_err()
+    );
+    let mut tampered_proof = proof.clone();
+    tampered_proof[0] ^= 1;
+    assert!(Bls12CircuitRunner::verify_with_vk(
+        &vk_from_bytes,
+        &params,
+        &circuit_inputs.4,
+        &tampered_proof
+    )
+    .is_err());
+
+    let pk_bytes = Bls12CircuitRunner::pk_to_bytes(&pk, SerdeFormat::RawBytes);
+    let pk_from_bytes = Bls12CircuitRunner::pk_from_bytes(
+        &pk_bytes,
+        SerdeFormat::RawBytes,
+        Blake2bInstanceMode::Public,
+    )
+    .unwrap();
+    assert_eq!(Bls12CircuitRunner::pk_to_bytes(&pk_from_bytes, SerdeFormat::RawBytes), pk_bytes);
+
+    let params_bytes =
+        Bls12CircuitRunner::params_to_bytes(&params, SerdeFormat::Processed).unwrap();
+    let params_from_bytes =
+        Bls12CircuitRunner::params_from_bytes(&params_bytes, SerdeFormat::Processed).unwrap();
+    assert_eq!(
+        Bls12CircuitRunner::params_to_bytes(&params_from_bytes, SerdeFormat::Processed).unwrap(),
+        params_bytes
+    );
+}
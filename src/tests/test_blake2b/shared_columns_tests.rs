@@ -0,0 +1,48 @@
+use super::chip_test_circuit::{ChipColumns, ChipTestCircuit};
+use super::*;
+use midnight_proofs::dev::{MockProver, VerifyFailure};
+use midnight_proofs::plonk::Circuit;
+
+fn shared_columns_circuit(fill_range_table: bool) -> (ChipTestCircuit, Vec<Fq>) {
+    let input = b"shared columns".to_vec();
+    let expected_output = expected_output_for(&input, 32);
+    let circuit = ChipTestCircuit {
+        input,
+        output_size: 32,
+        columns: ChipColumns::Shared { fill_range_table },
+    };
+    (circuit, expected_output)
+}
+
+#[test]
+fn test_blake2b_with_shared_columns_and_range_table() {
+    let (circuit, expected_output) = shared_columns_circuit(true);
+
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    prover.verify().unwrap();
+}
+
+#[test]
+fn test_blake2b_with_shared_columns_does_not_add_advice_columns() {
+    let (circuit, _) = shared_columns_circuit(true);
+    let mut meta = ConstraintSystem::<Fq>::default();
+    ChipTestCircuit::configure_with_params(&mut meta, circuit.params());
+    assert_eq!(meta.num_advice_columns(), 9);
+}
+
+#[test]
+fn test_blake2b_with_shared_columns_does_not_fill_the_range_table() {
+    let (circuit, expected_output) = shared_columns_circuit(false);
+
+    let prover = MockProver::run(&circuit, vec![expected_output]).unwrap();
+    let failures = prover.verify().unwrap_err();
+
+    // Only the range checks against the empty table fail
+    assert!(!failures.is_empty());
+    for failure in failures {
+        assert!(
+            matches!(&failure, VerifyFailure::Lookup { name, .. } if name.starts_with("lookup limb")),
+            "Unexpected failure: {failure}"
+        );
+    }
+}
@@ -1,5 +1,6 @@
-use crate::usage_utils::circuit_runner::CircuitRunner;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
 use super::*;
+use crate::error::Blake2bError;
 
 #[test]
 fn test_blake2b_single_empty_block_positive() {
@@ -9,9 +10,12 @@ fn test_blake2b_single_empty_block_positive() {
     let expected_output_state = correct_output_for_empty_input_64();
 
     let circuit =
-        CircuitRunner::create_circuit_for_inputs(input, input_size, vec![], 0, output_size);
-    let prover = CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit);
-    CircuitRunner::verify_mock_prover(prover);
+        Bls12CircuitRunner::create_circuit_for_inputs(input, input_size, vec![], 0, output_size)
+            .unwrap();
+    let prover =
+        Bls12CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit)
+            .unwrap();
+    Bls12CircuitRunner::verify_mock_prover(prover).unwrap();
 }
 
 #[test]
@@ -24,9 +28,12 @@ fn test_blake2b_single_empty_block_negative() {
     expected_output_state[7] = Fq::from(14u64); // Wrong value
 
     let circuit =
-        CircuitRunner::create_circuit_for_inputs(input, input_size, vec![], 0, output_size);
-    let prover = CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit);
-    CircuitRunner::verify_mock_prover(prover);
+        Bls12CircuitRunner::create_circuit_for_inputs(input, input_size, vec![], 0, output_size)
+            .unwrap();
+    let prover =
+        Bls12CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit)
+            .unwrap();
+    Bls12CircuitRunner::verify_mock_prover(prover).unwrap();
 }
 
 fn correct_output_for_empty_input_64() -> [Fq; 64] {
@@ -97,3 +104,18 @@ fn correct_output_for_empty_input_64() -> [Fq; 64] {
         Fq::from(206),
     ]
 }
+
+#[test]
+fn test_blake2b_input_that_is_not_a_byte_is_rejected() {
+    let input = vec![value_for(256u64)];
+    let expected_output_state = [Fq::ZERO; 64];
+
+    let circuit = Bls12CircuitRunner::create_circuit_for_inputs(input, 1, vec![], 0, 64).unwrap();
+    let result =
+        Bls12CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit);
+    let expected_message = Blake2bError::NonByteInput.to_string();
+    assert!(matches!(
+        result,
+        Err(Blake2bError::Synthesis(Error::Synthesis(message))) if message == expected_message
+    ));
+}
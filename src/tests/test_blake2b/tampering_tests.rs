@@ -0,0 +1,212 @@
+//! Soundness tests against a malicious prover. They synthesize a real hash and change the values
+//! of chosen advice cells before they reach the [MockProver]. Every such mutation must be rejected
+//! by `verify`; a cell whose mutation goes undetected is an under-constrained cell. Besides adding
+//! one to a cell, which any gate that determines it rejects, some mutations preserve the gates, by
+//! negating a cell or by compensating its change in the cells it is paired with, so that only the
+//! range checks can reject them.
+
+use super::recorded_layout::{
+    abc_circuit_and_instance, mock_prover_accepts, mock_prover_failures, recorded_layout,
+    AdviceCell, Mutation, RecordedLayout,
+};
+use super::*;
+use ff::PrimeField;
+use midnight_proofs::dev::VerifyFailure;
+use std::collections::BTreeSet;
+
+fn first_cell_annotated(cells: &[AdviceCell], annotation: &str) -> AdviceCell {
+    cells
+        .iter()
+        .find(|cell| cell.annotation == annotation)
+        .unwrap_or_else(|| panic!("no advice cell is annotated '{annotation}'"))
+        .clone()
+}
+
+/// The cells of the decomposed word in 'row', if it holds one: its full number and its limbs,
+/// from the least significant one.
+fn word_at(cells: &[AdviceCell], row: usize) -> Option<(AdviceCell, Vec<AdviceCell>)> {
+    let in_row = |annotation: &str| {
+        let mut cells: Vec<_> = cells
+            .iter()
+            .filter(|cell| cell.row == row && cell.annotation == annotation)
+            .cloned()
+            .collect();
+        cells.sort_by_key(|cell| cell.column);
+        cells
+    };
+    let (full_number, limbs) = (in_row("full number"), in_row("limb"));
+    (full_number.len() == 1 && limbs.len() == 8).then(|| (full_number[0].clone(), limbs))
+}
+
+fn is_range_check_of(failure: &VerifyFailure, cell: &AdviceCell) -> bool {
+    matches!(failure, VerifyFailure::Lookup { name, .. }
+        if name.starts_with("lookup limb") && name.contains(&format!("index: {},", cell.column)))
+}
+
+fn assert_mutation_is_rejected(mutation: &Mutation) {
+    let (circuit, instance) = abc_circuit_and_instance();
+    assert!(
+        !mock_prover_accepts(&circuit, &instance, mutation),
+        "the mutation {mutation:?} went undetected"
+    );
+}
+
+/// The first cell of every (region, annotation, column) kind the chip assigns, among the ones
+/// 'is_candidate' accepts.
+fn representatives(
+    layout: &RecordedLayout,
+    is_candidate: impl Fn(&AdviceCell) -> bool,
+) -> Vec<AdviceCell> {
+    let mut seen_kinds = BTreeSet::new();
+    layout
+        .advice_cells
+        .iter()
+        .filter(|cell| is_candidate(cell))
+        .filter(|cell| {
+            seen_kinds.insert((cell.region.clone(), cell.annotation.clone(), cell.column))
+        })
+        .cloned()
+        .collect()
+}
+
+/// The cells among 'cells' whose 'mutation' is accepted by the [MockProver].
+fn undetected_mutations(
+    cells: &[AdviceCell],
+    mutation: impl Fn(&AdviceCell) -> Mutation,
+) -> Vec<AdviceCell> {
+    let (circuit, instance) = abc_circuit_and_instance();
+    cells
+        .iter()
+        .filter(|cell| mock_prover_accepts(&circuit, &instance, &mutation(cell)))
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn test_tampered_limb_is_rejected() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let cells = recorded_layout(&circuit, &instance).advice_cells;
+    assert_mutation_is_rejected(&Mutation::add_one(&first_cell_annotated(&cells, "limb")));
+}
+
+#[test]
+fn test_tampered_carry_is_rejected() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let cells = recorded_layout(&circuit, &instance).advice_cells;
+    assert_mutation_is_rejected(&Mutation::add_one(&first_cell_annotated(&cells, "carry")));
+}
+
+#[test]
+fn test_tampered_rotation_output_is_rejected() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let cells = recorded_layout(&circuit, &instance).advice_cells;
+    assert_mutation_is_rejected(&Mutation::add_one(&first_cell_annotated(
+        &cells,
+        "Full number rotation output",
+    )));
+    assert_mutation_is_rejected(&Mutation::add_one(&first_cell_annotated(
+        &cells,
+        "Limb rotation output",
+    )));
+}
+
+#[test]
+fn test_tampered_padding_byte_is_rejected() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let cells = recorded_layout(&circuit, &instance).advice_cells;
+    // The block is the first 128 input bytes copied into the limbs, so its last byte is padding.
+    let block_bytes: Vec<_> =
+        cells.iter().filter(|cell| cell.annotation == "Copied input byte").take(128).collect();
+    assert_eq!(block_bytes.len(), 128);
+    assert_mutation_is_rejected(&Mutation::add_one(block_bytes[127]));
+}
+
+/// Adds one to the first cell of every (region, annotation, column) kind the chip assigns.
+#[test]
+fn test_no_kind_of_advice_cell_is_under_constrained() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let representatives = representatives(&recorded_layout(&circuit, &instance), |_| true);
+
+    let undetected = undetected_mutations(&representatives, Mutation::add_one);
+    assert!(
+        undetected.is_empty(),
+        "{} of {} mutated cells went undetected: {undetected:?}",
+        undetected.len(),
+        representatives.len()
+    );
+}
+
+/// Replaces the value x of the first non-zero cell of every kind with p - x. A cell that is only
+/// constrained by a gate that holds for -x too, e.g. a square, would accept it.
+#[test]
+fn test_no_kind_of_advice_cell_accepts_its_negation() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let layout = recorded_layout(&circuit, &instance);
+    let representatives = representatives(&layout, |cell| layout.value_of(cell) != Fq::ZERO);
+
+    let undetected = undetected_mutations(&representatives, |cell| Mutation::negate(cell, &layout));
+    assert!(
+        undetected.is_empty(),
+        "{} of {} negated cells went undetected: {undetected:?}",
+        undetected.len(),
+        representatives.len()
+    );
+}
+
+/// Adds 256 to a limb and subtracts one from the next limb, which preserves the decomposition of
+/// the word. Neither limb is copied anywhere, so only the lookups can reject it.
+#[test]
+fn test_out_of_range_limb_with_the_same_decomposition_is_rejected() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let layout = recorded_layout(&circuit, &instance);
+    let copied: BTreeSet<(usize, usize)> = layout
+        .copies
+        .iter()
+        .flat_map(|(left, right)| [left, right])
+        .map(|(column, row)| (column.index(), *row))
+        .collect();
+    let is_copied = |cell: &AdviceCell| copied.contains(&(cell.column, cell.row));
+    // Two consecutive limbs of a word, neither of them copied
+    let (low, high) = layout
+        .advice_cells
+        .iter()
+        .filter(|cell| cell.annotation == "full number")
+        .filter_map(|cell| word_at(&layout.advice_cells, cell.row))
+        .flat_map(|(_, limbs)| {
+            let pairs: Vec<_> =
+                limbs.windows(2).map(|pair| (pair[0].clone(), pair[1].clone())).collect();
+            pairs
+        })
+        .find(|(low, high)| !is_copied(low) && !is_copied(high))
+        .expect("some limbs should not be copied");
+
+    let mutation = Mutation::default().add(&low, Fq::from(256)).add(&high, -Fq::ONE);
+    let failures = mock_prover_failures(&circuit, &instance, &mutation);
+
+    assert!(failures.iter().any(|failure| is_range_check_of(failure, &low)));
+    assert!(
+        failures.iter().all(|failure| matches!(failure, VerifyFailure::Lookup { .. })),
+        "the mutation should only break lookups: {failures:?}"
+    );
+}
+
+/// Flips a carry, which keeps it a bit, and moves the difference to the sum and its most
+/// significant limb, which preserves the addition and the decomposition of the sum. The limb ends
+/// up out of range, so its range check must reject it.
+#[test]
+fn test_flipped_carry_with_the_same_sum_is_rejected() {
+    let (circuit, instance) = abc_circuit_and_instance();
+    let layout = recorded_layout(&circuit, &instance);
+    let carry = first_cell_annotated(&layout.advice_cells, "carry");
+    // The carry is assigned in the row of the second operand, right before the sum.
+    let (sum, sum_limbs) = word_at(&layout.advice_cells, carry.row + 1).expect("the sum is a word");
+
+    let flip = Fq::ONE - layout.value_of(&carry).double();
+    let mutation = Mutation::default()
+        .add(&carry, flip)
+        .add(&sum, -flip * Fq::from_u128(1 << 64))
+        .add(&sum_limbs[7], -flip * Fq::from(256));
+    let failures = mock_prover_failures(&circuit, &instance, &mutation);
+
+    assert!(failures.iter().any(|failure| is_range_check_of(failure, &sum_limbs[7])));
+}
@@ -1,5 +1,6 @@
-use crate::usage_utils::circuit_runner::CircuitRunner;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
 use super::*;
+use crate::error::Blake2bError;
 
 #[test]
 fn test_blake2b_circuit_can_verify_an_output_of_length_1() {
@@ -42,23 +43,15 @@ fn test_blake2b_circuit_can_verify_an_output_of_length_32_negative() {
 }
 
 #[test]
-#[should_panic(expected = "Output size must be between 1 and 64 bytes")]
 fn test_blake2b_circuit_should_receive_an_output_length_less_or_equal_64() {
-    let output_size = 65;
-    let input = vec![];
-    let input_size = 0;
-    let expected_output_state = [Fq::ZERO; 65];
-    run_variable_output_length_test(output_size, input, input_size, expected_output_state);
+    let result = Bls12CircuitRunner::create_circuit_for_inputs(vec![], 0, vec![], 0, 65);
+    assert!(matches!(result, Err(Blake2bError::InvalidOutputSize(65))));
 }
 
 #[test]
-#[should_panic(expected = "Output size must be between 1 and 64 bytes")]
 fn test_blake2b_circuit_should_receive_an_output_length_bigger_or_equal_1() {
-    let output_size = 0;
-    let input = vec![];
-    let input_size = 0;
-    let expected_output_state = [Fq::ZERO; 65];
-    run_variable_output_length_test(output_size, input, input_size, expected_output_state);
+    let result = Bls12CircuitRunner::create_circuit_for_inputs(vec![], 0, vec![], 0, 0);
+    assert!(matches!(result, Err(Blake2bError::InvalidOutputSize(0))));
 }
 
 fn run_variable_output_length_test<const OUT_SIZE: usize>(
@@ -68,9 +61,12 @@ fn run_variable_output_length_test<const OUT_SIZE: usize>(
     expected_output_state: [Fq; OUT_SIZE],
 ) {
     let circuit =
-        CircuitRunner::create_circuit_for_inputs(input, input_size, vec![], 0, output_size);
-    let prover = CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit);
-    CircuitRunner::verify_mock_prover(prover);
+        Bls12CircuitRunner::create_circuit_for_inputs(input, input_size, vec![], 0, output_size)
+            .unwrap();
+    let prover =
+        Bls12CircuitRunner::mock_prove_with_public_inputs_ref(&expected_output_state, &circuit)
+            .unwrap();
+    Bls12CircuitRunner::verify_mock_prover(prover).unwrap();
 }
 
 fn correct_output_for_empty_input_1() -> [Fq; 1] {
@@ -1,5 +1,5 @@
 use serde::Deserialize;
-use crate::usage_utils::circuit_runner::CircuitRunner;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
 
 #[derive(Deserialize, Debug)]
 struct TestCase {
@@ -10,7 +10,8 @@ struct TestCase {
 }
 
 pub(crate) fn run_test(input: &String, key: &String, expected: &String) {
-    CircuitRunner::mocked_preprocess_inputs_synthesize_prove_and_verify(input, key, expected);
+    Bls12CircuitRunner::mocked_preprocess_inputs_synthesize_prove_and_verify(input, key, expected)
+        .unwrap();
 }
 
 #[test]
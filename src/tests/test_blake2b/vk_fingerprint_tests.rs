@@ -0,0 +1,66 @@
+//! The golden fingerprints of 'vk_fingerprints.json' pin the verifying keys of representative
+//! sizes. A change that makes them drift breaks the circuits already deployed, so it has to update
+//! the file along with the version of the crate.
+
+use crate::error::Blake2bError;
+use crate::usage_utils::vk_fingerprint::vk_fingerprint;
+use serde::Deserialize;
+
+const GOLDEN_FINGERPRINTS: &str = "./vk_fingerprints.json";
+
+#[derive(Deserialize, Debug)]
+struct GoldenFingerprints {
+    /// Version of the crate the fingerprints were recorded for.
+    version: String,
+    fingerprints: Vec<GoldenFingerprint>,
+}
+
+#[derive(Deserialize, Debug)]
+struct GoldenFingerprint {
+    input_len: usize,
+    key_len: usize,
+    output_len: usize,
+    fingerprint: String,
+}
+
+fn golden_fingerprints() -> GoldenFingerprints {
+    let file_content = std::fs::read_to_string(GOLDEN_FINGERPRINTS).expect("Failed to read file");
+    serde_json::from_str(&file_content).expect("Failed to parse JSON")
+}
+
+#[test]
+fn test_vk_fingerprints_match_the_golden_ones() {
+    let golden = golden_fingerprints();
+
+    let drifted: Vec<String> = golden
+        .fingerprints
+        .iter()
+        .filter_map(|case| {
+            let fingerprint =
+                hex::encode(vk_fingerprint(case.input_len, case.key_len, case.output_len).unwrap());
+            (fingerprint != case.fingerprint).then(|| {
+                format!(
+                    "input_len {}, key_len {}, output_len {}: {fingerprint}",
+                    case.input_len, case.key_len, case.output_len
+                )
+            })
+        })
+        .collect();
+    assert!(
+        drifted.is_empty(),
+        "The verifying keys changed. If this is intended, record the new fingerprints in \
+         {GOLDEN_FINGERPRINTS} and bump the version of the crate:\n{}",
+        drifted.join("\n")
+    );
+}
+
+#[test]
+fn test_golden_fingerprints_are_recorded_for_the_current_version() {
+    assert_eq!(golden_fingerprints().version, env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_vk_fingerprint_rejects_invalid_sizes() {
+    assert!(matches!(vk_fingerprint(0, 0, 65), Err(Blake2bError::InvalidOutputSize(65))));
+    assert!(matches!(vk_fingerprint(0, 65, 64), Err(Blake2bError::InvalidKeySize(65))));
+}
@@ -0,0 +1,33 @@
+use crate::blake2b::witness::Blake2bWitness;
+use blake2b_simd::Params;
+
+#[test]
+fn test_native_witness_digest_matches_blake2b_for_different_sizes() {
+    for (input_size, key_size, output_size) in
+        [(0, 0, 64), (0, 32, 64), (1, 0, 32), (128, 0, 64), (129, 64, 64), (300, 17, 7)]
+    {
+        let input: Vec<u8> = (0..input_size).map(|i| i as u8).collect();
+        let key: Vec<u8> = (0..key_size).map(|i| (255 - i) as u8).collect();
+
+        let witness = Blake2bWitness::generate(&input, &key, output_size);
+        let digest: Vec<u8> = witness
+            .compressions
+            .last()
+            .unwrap()
+            .new_state
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .take(output_size)
+            .collect();
+
+        let expected = Params::new().hash_length(output_size).key(&key).hash(&input);
+        assert_eq!(digest, expected.as_bytes());
+    }
+}
+
+#[test]
+fn test_native_witness_has_one_compression_per_block() {
+    assert_eq!(Blake2bWitness::generate(&[], &[], 64).compressions.len(), 1);
+    assert_eq!(Blake2bWitness::generate(&[0; 256], &[], 64).compressions.len(), 2);
+    assert_eq!(Blake2bWitness::generate(&[0; 257], &[1], 64).compressions.len(), 4);
+}
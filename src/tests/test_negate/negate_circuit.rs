@@ -78,6 +78,7 @@ impl<F: PrimeField> Circuit<F> for NegateCircuit<F> {
                     &mut region,
                     &mut offset,
                     &cell,
+                    self.value.map(|v| Blake2bWord(!v.0)),
                     config.full_number_u64,
                 )?;
                 let fixed_cell = region.assign_fixed(
@@ -1,5 +1,7 @@
 use super::*;
+use crate::blake2b::blake2b_chip::RangeCheckTable;
 use crate::tests::Decompose8Config;
+use crate::base_operations::RangeTable;
 use midnight_proofs::circuit::SimpleFloorPlanner;
 use midnight_proofs::plonk::Circuit;
 use std::array;
@@ -7,6 +9,7 @@ use std::array;
 pub(crate) struct AdditionMod64Circuit8Bits<F: PrimeField> {
     _ph: PhantomData<F>,
     trace: [[Value<F>; 9]; 3],
+    range_check_table: RangeCheckTable,
 }
 
 #[derive(Clone, Debug)]
@@ -18,22 +21,39 @@ pub(crate) struct AdditionMod64Config8Bits<F: PrimeField + Clone> {
 
 impl<F: PrimeField> Circuit<F> for AdditionMod64Circuit8Bits<F> {
     type Config = AdditionMod64Config8Bits<F>;
-    type Params = ();
+    type Params = RangeCheckTable;
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
         Self {
             _ph: PhantomData,
             trace: [[Value::unknown(); 9]; 3],
+            range_check_table: self.range_check_table,
         }
     }
 
-    #[allow(unused_variables)]
+    fn params(&self) -> Self::Params {
+        self.range_check_table
+    }
+
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::configure_with_params(meta, RangeCheckTable::default())
+    }
+
+    #[allow(unused_variables)]
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<F>,
+        range_check_table: Self::Params,
+    ) -> Self::Config {
         let full_number_u64 = meta.advice_column();
         let limbs: [Column<Advice>; 8] = array::from_fn(|_| meta.advice_column());
 
-        let decompose_8_config = Decompose8Config::configure(meta, full_number_u64, limbs);
+        let range_table = match range_check_table {
+            RangeCheckTable::Dedicated => RangeTable::Range(meta.lookup_table_column()),
+            RangeCheckTable::Xor => RangeTable::Xor(array::from_fn(|_| meta.lookup_table_column())),
+        };
+        let decompose_8_config =
+            Decompose8Config::configure_with_range_table(meta, full_number_u64, limbs, range_table);
 
         let sum_8bits_config = AdditionMod64Config::configure(
             meta,
@@ -68,9 +88,17 @@ impl<F: PrimeField> Circuit<F> for AdditionMod64Circuit8Bits<F> {
 
 impl<F: PrimeField> AdditionMod64Circuit8Bits<F> {
     pub(crate) fn new_for_trace(trace: [[Value<F>; 9]; 3]) -> Self {
+        Self::new_for_trace_with_range_check_table(trace, RangeCheckTable::default())
+    }
+
+    pub(crate) fn new_for_trace_with_range_check_table(
+        trace: [[Value<F>; 9]; 3],
+        range_check_table: RangeCheckTable,
+    ) -> Self {
         Self {
             _ph: PhantomData,
             trace,
+            range_check_table,
         }
     }
 }
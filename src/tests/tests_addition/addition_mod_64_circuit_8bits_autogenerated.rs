@@ -100,12 +100,16 @@ impl<F: PrimeField> Circuit<F> for AdditionMod64Circuit8BitsAutogenerated<F> {
                 offset += 1;
 
                 let decompose = &config.add_config.decompose_8_config;
+                let sum_and_carry =
+                    self.value_a.zip(self.value_b).map(|(a, b)| a.0.overflowing_add(b.0));
                 let result_and_carry =
                     config.add_config.sum_8bits_config.generate_addition_rows_from_cells(
                         &mut region,
                         &mut offset,
                         &a_cell,
                         &b_cell,
+                        sum_and_carry.map(|(sum, _)| Blake2bWord(sum)),
+                        sum_and_carry.map(|(_, carry)| carry),
                         false,
                         config.full_number_u64,
                         config.limbs,
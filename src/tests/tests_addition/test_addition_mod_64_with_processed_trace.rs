@@ -1,11 +1,21 @@
+use crate::blake2b::blake2b_chip::RangeCheckTable;
 use crate::tests::tests_addition::addition_mod_64_circuit_8bits::AdditionMod64Circuit8Bits;
-use midnight_proofs::dev::MockProver;
+use midnight_proofs::dev::{MockProver, VerifyFailure};
 use midnight_curves::bls12_381::Fq;
 use rand::Rng;
 use crate::tests::{generate_row_8bits, value_for, zero};
 
 #[test]
 fn test_positive_addition_with_0() {
+    positive_addition_with_0(RangeCheckTable::Dedicated);
+}
+
+#[test]
+fn test_positive_addition_with_0_with_xor_range_check_table() {
+    positive_addition_with_0(RangeCheckTable::Xor);
+}
+
+fn positive_addition_with_0(range_check_table: RangeCheckTable) {
     // This value is used to assigned in cells where the value is not relevant for the circuit
     // it is zero, but it could be any value
     let unconstrained_value = zero();
@@ -34,7 +44,10 @@ fn test_positive_addition_with_0() {
             zero(),
         ],
     ];
-    let circuit = AdditionMod64Circuit8Bits::<Fq>::new_for_trace(trace);
+    let circuit = AdditionMod64Circuit8Bits::<Fq>::new_for_trace_with_range_check_table(
+        trace,
+        range_check_table,
+    );
     let prover = MockProver::run(&circuit, vec![]).unwrap();
     prover.verify().unwrap();
 
@@ -136,18 +149,39 @@ fn test_negative_sum_correct_but_unnecessary_carry() {
 }
 
 #[test]
-#[should_panic]
 fn test_negative_sum_correct_but_decomposition_exceedes_range_check() {
-    // This should panic because, although the sum is correct,
-    // the result does not respect the max sizes
+    negative_sum_correct_but_decomposition_exceedes_range_check(RangeCheckTable::Dedicated);
+}
+
+#[test]
+fn test_negative_sum_correct_but_decomposition_exceedes_range_check_with_xor_range_check_table() {
+    negative_sum_correct_but_decomposition_exceedes_range_check(RangeCheckTable::Xor);
+}
+
+fn negative_sum_correct_but_decomposition_exceedes_range_check(range_check_table: RangeCheckTable) {
+    // The sum, the carry and the decomposition of the result are correct, but the result is
+    // decomposed as 511 + 0 * 2^8 instead of 255 + 1 * 2^8, so only the range check of its first
+    // limb should fail
     let mut trace = [
         generate_row_8bits::<u64, Fq>(1 << 8),
-        generate_row_8bits::<u128, Fq>((1 << 8) - 1),
+        generate_row_8bits::<u64, Fq>((1 << 8) - 1),
         generate_row_8bits::<u64, Fq>((1 << 9) - 1),
     ];
-    trace[0][1] = value_for(1u16 << 8);
-    trace[0][2] = value_for(0u8);
-    let circuit = AdditionMod64Circuit8Bits::<Fq>::new_for_trace(trace);
+    trace[1][1] = zero();
+    trace[2][1] = value_for((1u16 << 9) - 1);
+    trace[2][2] = zero();
+    let circuit = AdditionMod64Circuit8Bits::<Fq>::new_for_trace_with_range_check_table(
+        trace,
+        range_check_table,
+    );
     let prover = MockProver::run(&circuit, vec![]).unwrap();
-    prover.verify().unwrap();
+    let failures = prover.verify().unwrap_err();
+
+    assert!(!failures.is_empty());
+    for failure in failures {
+        assert!(
+            matches!(&failure, VerifyFailure::Lookup { name, .. } if name.starts_with("lookup limb")),
+            "Unexpected failure: {failure}"
+        );
+    }
 }
@@ -100,6 +100,7 @@ impl<F: PrimeField, const T: usize> Circuit<F> for LimbRotationCircuitAutogenera
                         &mut offset,
                         input_row,
                         limbs_to_rotate_to_the_right,
+                        self.input.map(|v| Blake2bWord(v.0.rotate_right(T as u32))),
                         config.full_number_u64,
                         config.limbs,
                     )?;
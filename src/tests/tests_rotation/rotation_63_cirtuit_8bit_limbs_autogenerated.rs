@@ -97,6 +97,7 @@ impl<F: PrimeField> Circuit<F> for Rotation63Circuit8bitLimbsAutogenerated<F> {
                         &mut region,
                         &mut offset,
                         &a_row.full_number.clone(),
+                        self.input.map(|v| Blake2bWord(v.0.rotate_right(63))),
                         config.full_number_u64,
                         config.limbs,
                     )?;
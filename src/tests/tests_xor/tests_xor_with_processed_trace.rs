@@ -1,4 +1,5 @@
 use super::*;
+use crate::blake2b::blake2b_chip::RangeCheckTable;
 use crate::tests::tests_xor::xor_circuit::XorCircuit;
 use midnight_proofs::dev::MockProver;
 use rand::Rng;
@@ -37,6 +38,15 @@ fn test_positive_random_xor_with_0() {
 
 #[test]
 fn test_positive_random_xor() {
+    positive_random_xor(RangeCheckTable::Dedicated);
+}
+
+#[test]
+fn test_positive_random_xor_with_xor_range_check_table() {
+    positive_random_xor(RangeCheckTable::Xor);
+}
+
+fn positive_random_xor(range_check_table: RangeCheckTable) {
     let mut rng = rand::thread_rng();
     let n1: u64 = rng.gen();
     let n2: u64 = rng.gen();
@@ -46,7 +56,8 @@ fn test_positive_random_xor() {
         row_decomposed_in_8_limbs_from_u64(n1 ^ n2), // a xor b
     ];
 
-    let circuit = XorCircuit::<Fq>::new_for_trace(valid_xor_trace);
+    let circuit =
+        XorCircuit::<Fq>::new_for_trace_with_range_check_table(valid_xor_trace, range_check_table);
     let prover = MockProver::run(&circuit, vec![]).unwrap();
 
     prover.verify().unwrap();
@@ -103,6 +114,16 @@ fn test_bad_decomposition_in_8_bit_limbs() {
 #[test]
 #[should_panic]
 fn test_bad_range_check_limb_u8() {
+    bad_range_check_limb_u8(RangeCheckTable::Dedicated);
+}
+
+#[test]
+#[should_panic]
+fn test_bad_range_check_limb_u8_with_xor_range_check_table() {
+    bad_range_check_limb_u8(RangeCheckTable::Xor);
+}
+
+fn bad_range_check_limb_u8(range_check_table: RangeCheckTable) {
     let out_of_range_decomposition_row = [
         value_for((1u32 << 16) - 1),
         value_for((1u32 << 16) - 1),
@@ -121,7 +142,10 @@ fn test_bad_range_check_limb_u8() {
         out_of_range_decomposition_row,           // a xor b
     ];
 
-    let circuit = XorCircuit::<Fq>::new_for_trace(badly_decomposed_xor_trace);
+    let circuit = XorCircuit::<Fq>::new_for_trace_with_range_check_table(
+        badly_decomposed_xor_trace,
+        range_check_table,
+    );
 
     let prover = MockProver::run(&circuit, vec![]).unwrap();
     prover.verify().unwrap();
@@ -1,6 +1,8 @@
 use super::*;
+use crate::blake2b::blake2b_chip::RangeCheckTable;
 use crate::tests::Decompose8Config;
 use crate::base_operations::xor::XorConfig;
+use crate::base_operations::RangeTable;
 use midnight_proofs::circuit::SimpleFloorPlanner;
 use midnight_proofs::plonk::Circuit;
 use std::array;
@@ -16,40 +18,68 @@ pub(crate) struct XorCircuitConfig<F: PrimeField> {
 pub(crate) struct XorCircuit<F: PrimeField> {
     _ph: PhantomData<F>,
     trace: [[Value<F>; 9]; 3],
+    range_check_table: RangeCheckTable,
 }
 
 impl<F: PrimeField> XorCircuit<F> {
     pub(crate) fn new_for_trace(trace: [[Value<F>; 9]; 3]) -> Self {
+        Self::new_for_trace_with_range_check_table(trace, RangeCheckTable::default())
+    }
+
+    pub(crate) fn new_for_trace_with_range_check_table(
+        trace: [[Value<F>; 9]; 3],
+        range_check_table: RangeCheckTable,
+    ) -> Self {
         Self {
             _ph: PhantomData,
             trace,
+            range_check_table,
         }
     }
 }
 
 impl<F: PrimeField> Circuit<F> for XorCircuit<F> {
     type Config = XorCircuitConfig<F>;
-    type Params = ();
+    type Params = RangeCheckTable;
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
         Self {
             _ph: PhantomData,
             trace: [[Value::unknown(); 9]; 3],
+            range_check_table: self.range_check_table,
         }
     }
 
+    fn params(&self) -> Self::Params {
+        self.range_check_table
+    }
+
     fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::configure_with_params(meta, RangeCheckTable::default())
+    }
+
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<F>,
+        range_check_table: Self::Params,
+    ) -> Self::Config {
         let full_number_u64 = meta.advice_column();
         let limbs: [Column<Advice>; 8] = array::from_fn(|_| meta.advice_column());
 
-        let decompose_8_config = Decompose8Config::configure(meta, full_number_u64, limbs);
-        let xor_config = XorConfig::configure(
+        let xor_table = array::from_fn(|_| meta.lookup_table_column());
+        let range_table = match range_check_table {
+            RangeCheckTable::Dedicated => RangeTable::Range(meta.lookup_table_column()),
+            RangeCheckTable::Xor => RangeTable::Xor(xor_table),
+        };
+        let decompose_8_config =
+            Decompose8Config::configure_with_range_table(meta, full_number_u64, limbs, range_table);
+        let xor_config = XorConfig::configure_with_table(
             meta,
             limbs,
             full_number_u64,
             limbs,
             decompose_8_config.q_decompose,
+            xor_table,
         );
 
         Self::Config {
@@ -67,7 +97,10 @@ impl<F: PrimeField> Circuit<F> for XorCircuit<F> {
     ) -> Result<(), Error> {
         config.decompose_8_config.populate_lookup_table(&mut layouter)?;
 
-        config.xor_config.populate_xor_lookup_table(&mut layouter)?;
+        // When the range checks use the xor table, it was already filled above
+        if self.range_check_table == RangeCheckTable::Dedicated {
+            config.xor_config.populate_xor_lookup_table(&mut layouter)?;
+        }
         config.xor_config.populate_xor_region(
             &mut layouter,
             self.trace,
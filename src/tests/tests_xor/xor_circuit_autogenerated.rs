@@ -115,7 +115,13 @@ impl<F: PrimeField> Circuit<F> for XorCircuitAutogenerated<F> {
                 let result = config
                     .xor_config
                     .xor_config
-                    .generate_xor_rows_from_cells(&mut region, &mut offset, &a_cell, &b_cell)?
+                    .generate_xor_rows_from_cells(
+                        &mut region,
+                        &mut offset,
+                        &a_cell,
+                        &b_cell,
+                        self.value_a.zip(self.value_b).map(|(a, b)| a ^ b),
+                    )?
                     .full_number
                     .clone();
 
@@ -8,18 +8,6 @@ use midnight_proofs::utils::rational::Rational;
 #[derive(Copy, Clone, Debug)]
 struct Bit(bool);
 
-impl Bit {
-    /// Creates a new [Bit] element. When the byte is created, it is constrained to be in the
-    /// range [0, 1] and its internal member is a boolean.
-    fn new_from_field<F: PrimeField>(field: F) -> Self {
-        let bi_v = get_word_biguint_from_le_field(field);
-        #[cfg(not(test))]
-        assert!(bi_v == BigUint::from(0u8) || bi_v == BigUint::from(1u8));
-        let bit = bi_v.to_bytes_le().first().copied().unwrap();
-        Bit(bit == 1)
-    }
-}
-
 /// Allows us to call the .assign_advice() method of the region with a Bit as its value
 impl<F: PrimeField> From<&Bit> for Rational<F> {
     fn from(value: &Bit) -> Self {
@@ -45,10 +33,9 @@ impl<F: PrimeField> AssignedBit<F> {
         annotation: &str,
         column: Column<Advice>,
         offset: usize,
-        value: Value<F>,
+        value: Value<bool>,
     ) -> Result<Self, Error> {
-        // Check value is in range
-        let bit_value = value.map(|v| Bit::new_from_field(v));
+        let bit_value = value.map(Bit);
         // Create AssignedCell with the same value but different type
         let assigned_bit =
             Self(region.assign_advice(|| annotation, column, offset, || bit_value)?);
@@ -1,4 +1,4 @@
-use super::*;
+use super::{get_word_u64_from_le_field, AssignedNative};
 use ff::PrimeField;
 use midnight_proofs::circuit::{AssignedCell, Cell, Region, Value};
 use midnight_proofs::plonk::{Advice, Column, Error};
@@ -10,20 +10,14 @@ use std::ops::{BitXor, Sub};
 pub(crate) struct Blake2bWord(pub u64);
 
 impl Blake2bWord {
-    /// Creates a new [Blake2bWord] element. When the Blake2bWord is created, it is constrained to be in the
-    /// range [0, 2^64 - 1].
-    pub(crate) fn new_from_field<F: PrimeField>(field: F) -> Self {
-        let bi_v = get_word_biguint_from_le_field(field);
-        #[cfg(not(test))]
-        assert!(bi_v <= BigUint::from((1u128 << 64) - 1));
-        let mut bytes = bi_v.to_bytes_le();
-        bytes.resize(8, 0);
-        u64::from_le_bytes(bytes.try_into().unwrap()).into()
-    }
-
     pub(crate) fn to_le_bytes(self) -> [u8; 8] {
         self.0.to_le_bytes()
     }
+
+    /// The word held by a field element, or [None] if it's not in the range [0, 2^64 - 1].
+    pub(crate) fn try_from_field<F: PrimeField>(field: F) -> Option<Self> {
+        get_word_u64_from_le_field(field).map(Self)
+    }
 }
 
 impl BitXor for Blake2bWord {
@@ -94,25 +88,6 @@ impl<F: PrimeField> AssignedBlake2bWord<F> {
         Ok(Self(result))
     }
 
-    /// Given a value that contains a field element, this method converts it into a Blake2bWord and
-    /// assigns the value into a cell. The word is range-checked both in circuit-building
-    /// time (synthesize) and constrained in the circuit. The idea is that only the base operations
-    /// can create an [AssignedBlake2bWord] from a Field value, since they're responsible to
-    /// activate the constraints over the cells in the trace. In this case, the [Decompose8] gate
-    /// is the responsible to create them.
-    pub(crate) fn assign_advice_word_from_field(
-        region: &mut Region<'_, F>,
-        annotation: &str,
-        column: Column<Advice>,
-        offset: usize,
-        value: Value<F>,
-    ) -> Result<Self, Error> {
-        // Check value is in range
-        let word_value = value.map(|v| Blake2bWord::new_from_field(v));
-        // Create AssignedCell with the same value but different type
-        Self::assign_advice_word(region, annotation, column, offset, word_value)
-    }
-
     /// Given a value that contains a Blake2bWord, this method assigns the value into a cell
     pub(crate) fn assign_advice_word(
         region: &mut Region<'_, F>,
@@ -131,4 +106,10 @@ impl<F: PrimeField> AssignedBlake2bWord<F> {
     pub(crate) fn cell(&self) -> Cell {
         self.0.cell()
     }
+
+    /// The same cell, seen as an [AssignedNative]. This is how words leave the chip, since
+    /// an [AssignedNative] can't become an [AssignedBlake2bWord] without a range check.
+    pub(crate) fn to_native(&self) -> AssignedNative<F> {
+        self.0.convert_to_native()
+    }
 }
@@ -10,13 +10,11 @@ use super::*;
 pub struct Byte(pub u8);
 
 impl Byte {
-    /// Creates a new [Byte] element. When the byte is created, it is constrained to be in the
-    /// range [0, 255].
-    pub(crate) fn new_from_field<F: PrimeField>(field: F) -> Self {
-        let bi_v = get_word_biguint_from_le_field(field);
-        #[cfg(not(test))]
-        assert!(bi_v <= BigUint::from(255u8)); //[zhiyong]: no need to check in CPU, since it will be constrained in the circuit anyway
-        Byte(bi_v.to_bytes_le().first().copied().unwrap())
+    /// Creates a new [Byte] element from a field element, or returns [None] if the field element
+    /// is not in the range [0, 255].
+    pub(crate) fn try_from_field<F: PrimeField>(field: F) -> Option<Self> {
+        let value = get_word_u64_from_le_field(field)?;
+        u8::try_from(value).ok().map(Byte)
     }
 }
 
@@ -43,7 +41,8 @@ pub struct AssignedByte<F: PrimeField>(AssignedCell<Byte, F>);
 
 impl<F: PrimeField> AssignedByte<F> {
     /// This method takes an [AssignedNative], copies it to another cell in the circuit as an
-    /// [AssignedByte]. The range-check is performed in synthesize time, but
+    /// [AssignedByte]. The byte value of the cell is provided by the caller, who computed it
+    /// natively, so no conversion from the field value is needed.
     /// WARNING: the caller of this method should allways constrain the value to be a byte in the
     /// circuit. That's why only the base operations can create an [AssignedByte] from a Field value,
     /// since they're responsible to activate the constraints over the cells in the trace.
@@ -53,9 +52,8 @@ impl<F: PrimeField> AssignedByte<F> {
         column: Column<Advice>,
         offset: usize,
         cell_to_copy: AssignedNative<F>,
+        byte_value: Value<Byte>,
     ) -> Result<Self, Error> {
-        // Check value is in range
-        let byte_value = cell_to_copy.value().map(|v| Byte::new_from_field(*v));
         // Create AssignedCell with the same value but different type
         let assigned_byte =
             Self(region.assign_advice(|| annotation, column, offset, || byte_value)?);
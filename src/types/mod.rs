@@ -9,7 +9,6 @@
 /// restriction to be in range.
 use ff::PrimeField;
 use midnight_proofs::circuit::AssignedCell;
-use num_bigint::BigUint;
 
 /// Native type for an [AssignedCell] that hasn't been constrained yet
 pub type AssignedNative<F> = AssignedCell<F, F>;
@@ -24,19 +23,19 @@ pub mod blake2b_word;
 pub mod row;
 
 /// Given a field element and a limb index in little endian form, this function checks that the
-/// field element is in range [0, 2^64-1]. If it's not, it will fail.
+/// field element is in range [0, 2^64-1]. If it's not, it returns [None].
 /// We assume that the internal representation of the field is in little endian form. If it's
 /// not, the result is undefined and probably incorrect.
-/// Finally, it returns a [BigUint] holding the field element value.
-fn get_word_biguint_from_le_field<F: PrimeField>(fe: F) -> BigUint {
+/// Finally, it returns a [u64] holding the field element value.
+fn get_word_u64_from_le_field<F: PrimeField>(fe: F) -> Option<u64> {
     let field_internal_representation = fe.to_repr(); // Should be in little-endian
     let (bytes, zeros) = field_internal_representation.as_ref().split_at(8);
 
     let field_is_out_of_range = zeros.iter().any(|&el| el != 0u8);
 
     if field_is_out_of_range {
-        panic!("Arguments to the function are incorrect")
+        None
     } else {
-        BigUint::from_bytes_le(bytes)
+        bytes.try_into().ok().map(u64::from_le_bytes)
     }
 }
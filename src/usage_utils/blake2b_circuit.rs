@@ -1,14 +1,109 @@
 //! This is an example circuit of how you should use the Blake2b chip
 
 use crate::blake2b::blake2b_chip::{Blake2bChip, Blake2bConfig};
+use crate::blake2b::utils::enforce_input_sizes;
+use crate::blake2b::NB_BLAKE2B_TABLE_ROWS;
+use crate::error::Blake2bError;
 use crate::types::AssignedNative;
 use ff::PrimeField;
 use midnight_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
 use midnight_proofs::plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Instance};
 use std::array;
+use std::ops::Range;
+
+/// Which values of the hash are instances of the circuit, and which of those are committed
+/// instances. A committed instance column is only known to the verifier through a commitment to
+/// it, so the proof can be linked to other proofs on the same commitment without revealing its
+/// values. Committed instance columns always come first, as midnight-proofs expects.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Blake2bInstanceMode {
+    /// A single public instance column with the digest.
+    #[default]
+    Public,
+    /// A single committed instance column with the digest.
+    CommittedDigest,
+    /// A committed instance column with the input bytes, followed by a public instance column
+    /// with the digest.
+    CommittedMessage,
+}
+
+impl Blake2bInstanceMode {
+    /// Number of committed instance columns of the circuit.
+    pub fn nb_committed_instances(&self) -> usize {
+        match self {
+            Self::Public => 0,
+            Self::CommittedDigest | Self::CommittedMessage => 1,
+        }
+    }
+}
+
+/// How the digest is laid out in its instance column.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DigestLayout {
+    /// One instance per byte of the digest.
+    #[default]
+    Bytes,
+    /// One instance per 8 bytes of the digest, packed into a 64-bit word in little-endian order
+    /// by [Blake2bChip::pack_digest].
+    PackedWords,
+}
+
+impl DigestLayout {
+    /// Number of instances taken by a digest of 'output_size' bytes.
+    pub fn nb_instances(&self, output_size: usize) -> usize {
+        match self {
+            Self::Bytes => output_size,
+            Self::PackedWords => output_size.div_ceil(8),
+        }
+    }
+
+    /// The instances of 'digest', as laid out in the instance column.
+    pub fn instances_for<F: PrimeField>(&self, digest: &[u8]) -> Vec<F> {
+        match self {
+            Self::Bytes => digest.iter().map(|byte| F::from(*byte as u64)).collect(),
+            Self::PackedWords => digest
+                .chunks(8)
+                .map(|bytes| {
+                    let mut word = [0u8; 8];
+                    word[..bytes.len()].copy_from_slice(bytes);
+                    F::from(u64::from_le_bytes(word))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A range of input bytes that are public, for statements like "I know 's' such that
+/// BLAKE2b(public_header || s) = d". The rest of the input stays private.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PublicInputBytes {
+    /// The bytes in this range of the input are instances. They follow the digest in its instance
+    /// column, in the order of the ranges, so they're committed along with it in
+    /// [Blake2bInstanceMode::CommittedDigest].
+    Instance(Range<usize>),
+    /// The input has these bytes from 'offset' on. They're fixed at keygen, so the keys only prove
+    /// inputs that have them.
+    Fixed {
+        /// Position of the first byte in the input.
+        offset: usize,
+        /// The fixed bytes.
+        bytes: Vec<u8>,
+    },
+}
+
+impl PublicInputBytes {
+    /// The range of the input covered by these bytes.
+    pub fn range(&self) -> Range<usize> {
+        match self {
+            Self::Instance(range) => range.clone(),
+            Self::Fixed { offset, bytes } => *offset..*offset + bytes.len(),
+        }
+    }
+}
 
 /// The struct of the circuit. It contains the input and key that will be hashed. Also
-/// the sizes of the input, key and output.
+/// the sizes of the input, key and output, the mode of its instances, the layout of the digest
+/// and the public input bytes.
 #[derive(Clone, Debug)]
 pub struct Blake2bCircuit<F: PrimeField> {
     /// The input and the key should be unknown for the verifier.
@@ -18,11 +113,16 @@ pub struct Blake2bCircuit<F: PrimeField> {
     input_size: usize,
     key_size: usize,
     output_size: usize,
+    instance_mode: Blake2bInstanceMode,
+    digest_layout: DigestLayout,
+    public_input: Vec<PublicInputBytes>,
 }
 
 impl<F: PrimeField> Circuit<F> for Blake2bCircuit<F> {
-    type Config = (Blake2bConfig, Column<Instance>);
-    type Params = ();
+    /// The config of the chip, the instance column of the digest and, when the message is
+    /// committed, the instance column of the message.
+    type Config = (Blake2bConfig, Column<Instance>, Option<Column<Instance>>);
+    type Params = Blake2bInstanceMode;
     type FloorPlanner = SimpleFloorPlanner;
 
     fn without_witnesses(&self) -> Self {
@@ -35,22 +135,56 @@ impl<F: PrimeField> Circuit<F> for Blake2bCircuit<F> {
             key: vec![Value::unknown(); key_size],
             key_size,
             output_size,
+            instance_mode: self.instance_mode,
+            digest_layout: self.digest_layout,
+            public_input: self.public_input.clone(),
         }
     }
 
-    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+    fn params(&self) -> Self::Params {
+        self.instance_mode
+    }
+
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<F>,
+        instance_mode: Self::Params,
+    ) -> Self::Config {
         let full_number_u64 = meta.advice_column();
         let limbs: [Column<Advice>; 8] = array::from_fn(|_| meta.advice_column());
         let constant_col = meta.fixed_column();
+        // The committed column has to be created first
+        let message = (instance_mode == Blake2bInstanceMode::CommittedMessage).then(|| {
+            let message = meta.instance_column();
+            meta.enable_equality(message);
+            message
+        });
         let expected_final_state = meta.instance_column();
         meta.enable_equality(expected_final_state);
-        (Blake2bChip::configure(meta, constant_col, full_number_u64, limbs), expected_final_state)
+        (
+            Blake2bChip::configure(meta, constant_col, full_number_u64, limbs),
+            expected_final_state,
+            message,
+        )
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Self::configure_with_params(meta, Blake2bInstanceMode::default())
     }
 
-    fn synthesize(
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let chip = Blake2bChip::new(&config.0);
+        self.synthesize_with_chip(config, layouter, chip)
+    }
+}
+
+impl<F: PrimeField> Blake2bCircuit<F> {
+    /// Synthesizes the circuit with the given 'chip', which must be built from the chip config in
+    /// 'config'. This lets the tools of the crate synthesize it with a chip that labels its rows.
+    pub(crate) fn synthesize_with_chip(
         &self,
-        config: Self::Config,
+        config: <Self as Circuit<F>>::Config,
         mut layouter: impl Layouter<F>,
+        chip: Blake2bChip<F>,
     ) -> Result<(), Error> {
         // The input bytes are assigned in the circuit before calling the hash function.
         // They're not constrained to be in the range [0,255] here, but they are when used inside
@@ -60,36 +194,212 @@ impl<F: PrimeField> Circuit<F> for Blake2bCircuit<F> {
             Self::assign_inputs_to_the_trace(config.0.clone(), &mut layouter, &self.input)?;
         let assigned_key =
             Self::assign_inputs_to_the_trace(config.0.clone(), &mut layouter, &self.key)?;
+        if let Some(message) = config.2 {
+            for (i, input_byte_cell) in assigned_input.iter().enumerate() {
+                layouter.constrain_instance(input_byte_cell.cell(), message, i)?;
+            }
+        }
+        self.constrain_public_input(&config, &mut layouter, &assigned_input)?;
 
-        // Initialising the chip and calling the hash.
-        let chip = Blake2bChip::new(&config.0);
+        // Loading the chip and calling the hash.
         chip.load(&mut layouter)?;
         let result = chip.hash(&mut layouter, &assigned_input, &assigned_key, self.output_size)?;
 
         // Assert results
-        for (i, global_state_byte_cell) in result.iter().enumerate().take(self.output_size) {
-            layouter.constrain_instance(global_state_byte_cell.cell(), config.1, i)?;
+        let digest = &result[..self.output_size];
+        match self.digest_layout {
+            DigestLayout::Bytes => {
+                for (i, global_state_byte_cell) in digest.iter().enumerate() {
+                    layouter.constrain_instance(global_state_byte_cell.cell(), config.1, i)?;
+                }
+            }
+            DigestLayout::PackedWords => {
+                for (i, word_cell) in chip.pack_digest(&mut layouter, digest)?.iter().enumerate() {
+                    layouter.constrain_instance(word_cell.cell(), config.1, i)?;
+                }
+            }
         }
         Ok(())
     }
-}
 
-impl<F: PrimeField> Blake2bCircuit<F> {
     /// This method creates a new instance of the circuit with the given input, key and output sizes.
+    /// It fails if the output or key sizes are not valid for Blake2b.
     pub fn new(
         input: Vec<Value<F>>,
         input_size: usize,
         key: Vec<Value<F>>,
         key_size: usize,
         output_size: usize,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, Blake2bError> {
+        Self::new_with_instance_mode(
+            input,
+            input_size,
+            key,
+            key_size,
+            output_size,
+            Blake2bInstanceMode::default(),
+        )
+    }
+
+    /// A circuit without witnesses for an input of 'input_size' bytes, a key of 'key_size' bytes
+    /// and an output of 'output_size' bytes, which is enough to generate its keys or analyse it.
+    /// It fails if the output or key sizes are not valid for Blake2b.
+    pub fn for_sizes(
+        input_size: usize,
+        key_size: usize,
+        output_size: usize,
+    ) -> Result<Self, Blake2bError> {
+        Self::new(
+            vec![Value::unknown(); input_size],
+            input_size,
+            vec![Value::unknown(); key_size],
+            key_size,
+            output_size,
+        )
+    }
+
+    /// Same as [Self::new], for a circuit whose instances follow 'instance_mode'.
+    pub fn new_with_instance_mode(
+        input: Vec<Value<F>>,
+        input_size: usize,
+        key: Vec<Value<F>>,
+        key_size: usize,
+        output_size: usize,
+        instance_mode: Blake2bInstanceMode,
+    ) -> Result<Self, Blake2bError> {
+        enforce_input_sizes(output_size, key_size)?;
+        Ok(Self {
             input,
             input_size,
             key,
             key_size,
             output_size,
+            instance_mode,
+            digest_layout: DigestLayout::default(),
+            public_input: vec![],
+        })
+    }
+
+    /// Marks bytes of the input as public, see [PublicInputBytes]. It fails if some range is not
+    /// within the input.
+    pub fn with_public_input(
+        mut self,
+        public_input: Vec<PublicInputBytes>,
+    ) -> Result<Self, Blake2bError> {
+        if let Some(range) = public_input
+            .iter()
+            .map(PublicInputBytes::range)
+            .find(|range| range.start > range.end || range.end > self.input_size)
+        {
+            return Err(Blake2bError::InvalidPublicInputRange(range));
+        }
+        self.public_input = public_input;
+        Ok(self)
+    }
+
+    /// Lays out the digest in its instance column as given by 'digest_layout'. The public input
+    /// bytes, if any, follow it.
+    pub fn with_digest_layout(mut self, digest_layout: DigestLayout) -> Self {
+        self.digest_layout = digest_layout;
+        self
+    }
+
+    /// The layout of the digest in its instance column.
+    pub fn digest_layout(&self) -> DigestLayout {
+        self.digest_layout
+    }
+
+    /// The mode of the instances of the circuit.
+    pub fn instance_mode(&self) -> Blake2bInstanceMode {
+        self.instance_mode
+    }
+
+    /// The public input bytes of the circuit.
+    pub fn public_input(&self) -> &[PublicInputBytes] {
+        &self.public_input
+    }
+
+    /// Whether both circuits have the same keys, so that they can be proven together. Only their
+    /// input and key values may differ.
+    pub fn has_same_shape(&self, other: &Self) -> bool {
+        self.input_size == other.input_size
+            && self.key_size == other.key_size
+            && self.output_size == other.output_size
+            && self.instance_mode == other.instance_mode
+            && self.digest_layout == other.digest_layout
+            && self.public_input == other.public_input
+    }
+
+    /// Number of rows used by the circuit for an input of 'input_size' bytes and a key of
+    /// 'key_size' bytes: the input and the key are stored 8 bytes per row, followed by the region
+    /// of the hash.
+    pub fn rows_required(input_size: usize, key_size: usize) -> usize {
+        input_size.div_ceil(8)
+            + key_size.div_ceil(8)
+            + Blake2bChip::<F>::rows_required(input_size, key_size)
+    }
+
+    /// The smallest k such that the circuit for an input of 'input_size' bytes and a key of
+    /// 'key_size' bytes fits in 2^k rows. Besides the rows of the circuit, these have to hold the
+    /// lookup tables of the chip and the rows reserved for blinding.
+    pub fn k_for(input_size: usize, key_size: usize) -> u32 {
+        Self::k_for_rows(Self::rows_required(input_size, key_size))
+    }
+
+    /// The smallest k such that this circuit fits in 2^k rows, see [Self::k_for]. A packed
+    /// digest takes one more row per word.
+    pub fn k(&self) -> u32 {
+        let digest_rows = match self.digest_layout {
+            DigestLayout::Bytes => 0,
+            DigestLayout::PackedWords => DigestLayout::PackedWords.nb_instances(self.output_size),
+        };
+        Self::k_for_rows(Self::rows_required(self.input_size, self.key_size) + digest_rows)
+    }
+
+    fn k_for_rows(rows: usize) -> u32 {
+        let mut meta = ConstraintSystem::<F>::default();
+        Self::configure(&mut meta);
+        let usable_rows = rows.max(NB_BLAKE2B_TABLE_ROWS);
+        (usable_rows + meta.blinding_factors() + 1).next_power_of_two().ilog2()
+    }
+
+    /// The input bytes in an [PublicInputBytes::Instance] range are copied to the digest instance
+    /// column, after the digest in its [DigestLayout], and those of a [PublicInputBytes::Fixed]
+    /// range are constrained to be equal to fixed cells.
+    fn constrain_public_input(
+        &self,
+        config: &<Self as Circuit<F>>::Config,
+        layouter: &mut impl Layouter<F>,
+        assigned_input: &[AssignedNative<F>],
+    ) -> Result<(), Error> {
+        let mut instance_row = self.digest_layout.nb_instances(self.output_size);
+        for public_input in &self.public_input {
+            match public_input {
+                PublicInputBytes::Instance(range) => {
+                    for input_byte_cell in &assigned_input[range.clone()] {
+                        layouter.constrain_instance(
+                            input_byte_cell.cell(),
+                            config.1,
+                            instance_row,
+                        )?;
+                        instance_row += 1;
+                    }
+                }
+                PublicInputBytes::Fixed { offset, bytes } => layouter.assign_region(
+                    || "Fixed input",
+                    |mut region| {
+                        for (input_byte_cell, byte) in assigned_input[*offset..].iter().zip(bytes) {
+                            region.constrain_constant(
+                                input_byte_cell.cell(),
+                                F::from(*byte as u64),
+                            )?;
+                        }
+                        Ok(())
+                    },
+                )?,
+            }
         }
+        Ok(())
     }
 
     /// Here the inputs are stored in the trace. It doesn't really matter how they're stored, this
@@ -102,23 +412,20 @@ impl<F: PrimeField> Blake2bCircuit<F> {
         let result = layouter.assign_region(
             || "Inputs",
             |mut region| {
-                let inner_result = input
+                input
                     .iter()
                     .enumerate()
                     .map(|(index, input_byte)| {
                         let row = index / 8;
                         let column = index % 8;
-                        region
-                            .assign_advice(
-                                || format!("Input column: {row}, row: {column}"),
-                                config.limbs[column],
-                                row,
-                                || *input_byte,
-                            )
-                            .unwrap()
+                        region.assign_advice(
+                            || format!("Input column: {row}, row: {column}"),
+                            config.limbs[column],
+                            row,
+                            || *input_byte,
+                        )
                     })
-                    .collect::<Vec<_>>();
-                Ok(inner_result)
+                    .collect::<Result<Vec<_>, _>>()
             },
         )?;
         Ok(result)
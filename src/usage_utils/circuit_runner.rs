@@ -1,189 +1,713 @@
 //! Circuit runner module for creating Blake2bCircuit, synthesizing, proving and verifying it.
 //! It can work with both Mock Prover and Real Prover.
+//!
+//! The runner is generic over the field the circuit is defined on and the polynomial commitment
+//! scheme of the proofs, so any curve supported by midnight-proofs can be used.
+//! [Bls12CircuitRunner] is the runner for KZG over BLS12-381, the one used by the tool, the
+//! example and the benches.
 
 use midnight_proofs::dev::MockProver;
 use midnight_curves::bls12_381::{Bls12, Fq};
+use midnight_curves::pairing::group::Curve;
+use midnight_curves::pairing::{Engine, MultiMillerLoop};
 use midnight_proofs::{
-    plonk::{create_proof, keygen_pk, keygen_vk_with_k, prepare, ProvingKey, VerifyingKey},
+    plonk::{
+        commit_to_instances, create_proof, keygen_pk, keygen_vk_with_k, prepare, ProvingKey,
+        VerifyingKey,
+    },
     poly::{
-        commitment::Guard,
-        kzg::{params::ParamsKZG, KZGCommitmentScheme},
+        commitment::{Guard, Params, PolynomialCommitmentScheme},
+        kzg::{msm::DualMSM, params::ParamsKZG, KZGCommitmentScheme},
     },
-    transcript::{CircuitTranscript, Transcript},
+    transcript::{CircuitTranscript, Hashable, Sampleable, Transcript},
 };
 use blake2b_simd::State as Blake2bState;
+use ff::{Field, FromUniformBytes, PrimeField, WithSmallOrderMulGroup};
+use midnight_curves::serde::SerdeObject;
 use midnight_proofs::circuit::Value;
 use midnight_proofs::plonk::Error;
-use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use midnight_proofs::utils::arithmetic::{CurveAffine, CurveExt};
+use midnight_proofs::utils::helpers::ProcessedSerdeObject;
+use midnight_proofs::utils::SerdeFormat;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufReader, BufWriter, Write};
+use std::marker::PhantomData;
+use std::path::Path;
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, Blake2bInstanceMode, PublicInputBytes};
+#[cfg(any(test, feature = "dev-graph"))]
+use crate::usage_utils::diagnostics::Blake2bDiagnostics;
+use serde::Serialize;
 
 /// The inputs for the Blake2bCircuit. This helps us to avoid passing multiple parameters to the
 /// methods that create circuits
-pub type Blake2bCircuitInputs = (Vec<Value<Fq>>, usize, Vec<Value<Fq>>, usize, [Fq; 64], usize);
+pub type Blake2bCircuitInputs<F> = (Vec<Value<F>>, usize, Vec<Value<F>>, usize, [F; 64], usize);
+
+/// A proof along with its public inputs, as a verifier receives them, and their sizes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProofArtifacts {
+    /// The proof, as written to the transcript.
+    pub proof: Vec<u8>,
+    /// The values of the public instance columns, one column after the other, each value in the
+    /// canonical encoding of the field ([PrimeField::to_repr]). The committed instance columns are
+    /// left out, since the verifier only gets their commitments.
+    pub public_inputs: Vec<u8>,
+    /// The sizes of the proof and of the public inputs.
+    pub stats: ProofSizeStats,
+}
 
-/// Circuit runner struct
+/// The sizes of a proof and of its public inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct ProofSizeStats {
+    /// Size of the proof, in bytes.
+    pub proof_bytes: usize,
+    /// Number of public field elements.
+    pub public_input_elements: usize,
+    /// Size of the encoded public inputs, in bytes.
+    pub public_input_bytes: usize,
+}
+
+impl ProofSizeStats {
+    /// Bytes sent to the verifier: the proof and its public inputs.
+    pub fn total_bytes(&self) -> usize {
+        self.proof_bytes + self.public_input_bytes
+    }
+}
+
+impl ProofArtifacts {
+    /// The artifacts of 'proof', whose public instance columns hold 'public_instances'.
+    pub fn new<F: PrimeField>(proof: Vec<u8>, public_instances: &[&[F]]) -> Self {
+        let public_inputs: Vec<u8> = public_instances
+            .iter()
+            .flat_map(|column| column.iter())
+            .flat_map(|value| value.to_repr().as_ref().to_vec())
+            .collect();
+        let stats = ProofSizeStats {
+            proof_bytes: proof.len(),
+            public_input_elements: public_instances.iter().map(|column| column.len()).sum(),
+            public_input_bytes: public_inputs.len(),
+        };
+        Self {
+            proof,
+            public_inputs,
+            stats,
+        }
+    }
+}
+
+/// Circuit runner struct, for circuits over the field 'F' proven with the commitment scheme 'CS'
 #[derive(Debug)]
-pub struct CircuitRunner;
+pub struct CircuitRunner<F, CS> {
+    _marker: PhantomData<(F, CS)>,
+}
+
+/// Circuit runner for KZG proofs over BLS12-381
+pub type Bls12CircuitRunner = CircuitRunner<Fq, KZGCommitmentScheme<Bls12>>;
 
 /// Circuit runner methods for Mock Prover
-impl CircuitRunner {
-    /// Preprocess inputs, synthesize, prove and verify the circuit using Mock Prover
+impl<F: PrimeField + FromUniformBytes<64> + Ord, CS> CircuitRunner<F, CS> {
+    /// Preprocess inputs, synthesize, prove and verify the circuit using Mock Prover. The input,
+    /// key and expected digest are hex strings. If the circuit is not satisfied, the error has
+    /// the diagnostics of `verify_mock_prover_with_diagnostics` with the dev-graph feature, and
+    /// the failures of [Self::verify_mock_prover] without it.
     pub fn mocked_preprocess_inputs_synthesize_prove_and_verify(
         input: &String,
         key: &String,
         expected: &String,
-    ) {
-        let circuit_inputs = Self::prepare_parameters_for_test(input, key, expected);
+    ) -> Result<(), Blake2bError> {
+        let circuit_inputs = Self::prepare_parameters_for_test(input, key, expected)?;
+
+        let circuit = Self::create_circuit_for_packed_inputs(circuit_inputs.clone())?;
+        let prover = Self::mock_prove_with_public_inputs_ref(&circuit_inputs.4, &circuit)?;
+        #[cfg(any(test, feature = "dev-graph"))]
+        return Self::verify_mock_prover_with_diagnostics(&prover, &circuit);
+        #[cfg(not(any(test, feature = "dev-graph")))]
+        return Self::verify_mock_prover(prover);
+    }
 
-        let circuit = Self::create_circuit_for_packed_inputs(circuit_inputs.clone());
-        let prover = Self::mock_prove_with_public_inputs_ref(&circuit_inputs.4, &circuit);
-        Self::verify_mock_prover(prover);
+    /// Verify the circuit using Mock Prover. If it fails, the error has a message for every
+    /// failure.
+    pub fn verify_mock_prover(prover: MockProver<F>) -> Result<(), Blake2bError> {
+        prover.verify().map_err(|failures| {
+            Blake2bError::Unsatisfied(failures.iter().map(|f| f.to_string()).collect())
+        })
     }
 
-    /// Verify the circuit using Mock Prover
-    pub fn verify_mock_prover(prover: MockProver<Fq>) {
-        prover.verify().unwrap()
+    /// Verify the circuit using Mock Prover. If it fails, the error has the block, round, mix and
+    /// operation of every failure, taken from the layout of 'circuit', which must be the circuit
+    /// the prover ran on. Only available with the dev-graph feature.
+    #[cfg(any(test, feature = "dev-graph"))]
+    pub fn verify_mock_prover_with_diagnostics(
+        prover: &MockProver<F>,
+        circuit: &Blake2bCircuit<F>,
+    ) -> Result<(), Blake2bError> {
+        // The layout is only recorded when there is something to diagnose
+        if let Err(failures) = prover.verify() {
+            let diagnostics = Blake2bDiagnostics::for_circuit(circuit)?;
+            let failures = diagnostics.diagnose_all(&failures);
+            return Err(Blake2bError::Unsatisfied(
+                failures.iter().map(|f| f.to_string()).collect(),
+            ));
+        }
+        Ok(())
     }
 
     /// Create and run the Mock Prover using public inputs
     pub fn mock_prove_with_public_inputs_ref(
-        expected_output_fields: &[Fq],
-        circuit: &Blake2bCircuit<Fq>,
-    ) -> MockProver<Fq> {
-        MockProver::run(circuit, vec![expected_output_fields.to_vec()]).unwrap()
+        expected_output_fields: &[F],
+        circuit: &Blake2bCircuit<F>,
+    ) -> Result<MockProver<F>, Blake2bError> {
+        Self::mock_prove_with_instances(vec![expected_output_fields.to_vec()], circuit)
+    }
+
+    /// Create and run the Mock Prover with the values of every instance column of the circuit,
+    /// in the order given by its [Blake2bInstanceMode]. The Mock Prover doesn't tell committed
+    /// instances apart from public ones.
+    pub fn mock_prove_with_instances(
+        instances: Vec<Vec<F>>,
+        circuit: &Blake2bCircuit<F>,
+    ) -> Result<MockProver<F>, Blake2bError> {
+        Ok(MockProver::run(circuit, instances)?)
     }
 
     /// Create circuit for the given inputs
     pub fn create_circuit_for_inputs(
-        input_values: Vec<Value<Fq>>,
+        input_values: Vec<Value<F>>,
         input_size: usize,
-        key_values: Vec<Value<Fq>>,
+        key_values: Vec<Value<F>>,
         key_size: usize,
         output_size: usize,
-    ) -> Blake2bCircuit<Fq> {
-        Blake2bCircuit::<Fq>::new(input_values, input_size, key_values, key_size, output_size)
+    ) -> Result<Blake2bCircuit<F>, Blake2bError> {
+        Blake2bCircuit::<F>::new(input_values, input_size, key_values, key_size, output_size)
+    }
+
+    /// Create circuit for the given inputs, where the bytes in 'public_input' are public. The
+    /// digest instance column of this circuit is given by [Self::public_instance_for].
+    pub fn create_circuit_with_public_input(
+        input_values: Vec<Value<F>>,
+        input_size: usize,
+        key_values: Vec<Value<F>>,
+        key_size: usize,
+        output_size: usize,
+        public_input: Vec<PublicInputBytes>,
+    ) -> Result<Blake2bCircuit<F>, Blake2bError> {
+        Blake2bCircuit::<F>::new(input_values, input_size, key_values, key_size, output_size)?
+            .with_public_input(public_input)
+    }
+
+    /// The values of the digest instance column of a circuit with public input bytes: the digest
+    /// followed by the bytes of its [PublicInputBytes::Instance] ranges, in order.
+    pub fn public_instance_for(expected_output_fields: &[F], public_input_bytes: &[u8]) -> Vec<F> {
+        expected_output_fields
+            .iter()
+            .copied()
+            .chain(public_input_bytes.iter().map(|byte| F::from(*byte as u64)))
+            .collect()
     }
 
     /// Create circuit for the given inputs. In this function the inputs are packed in a
     /// Blake2bCircuitInputs struct to avoid passing multiple parameters to the function
-    pub fn create_circuit_for_packed_inputs(ci: Blake2bCircuitInputs) -> Blake2bCircuit<Fq> {
-        Blake2bCircuit::<Fq>::new(ci.0, ci.1, ci.2, ci.3, ci.5)
+    pub fn create_circuit_for_packed_inputs(
+        ci: Blake2bCircuitInputs<F>,
+    ) -> Result<Blake2bCircuit<F>, Blake2bError> {
+        Blake2bCircuit::<F>::new(ci.0, ci.1, ci.2, ci.3, ci.5)
     }
 
     /// Convert the input, key and expected output in byte blocks
     /// For the input and key, blocks are made of values, since they are private inputs of the
-    /// circuit
+    /// circuit. It fails if any of them is not a valid hex string.
     pub fn prepare_parameters_for_test(
         input: &String,
         key: &String,
         expected: &String,
-    ) -> Blake2bCircuitInputs {
+    ) -> Result<Blake2bCircuitInputs<F>, Blake2bError> {
         // INPUT
-        let input_size = input.len() / 2; // Amount of bytes
-        let input_bytes = hex::decode(input).expect("Invalid hex string");
+        let input_bytes = hex::decode(input)?;
+        let input_size = input_bytes.len();
         let input_values =
-            input_bytes.iter().map(|x| Value::known(Fq::from(*x as u64))).collect::<Vec<_>>();
+            input_bytes.iter().map(|x| Value::known(F::from(*x as u64))).collect::<Vec<_>>();
 
         // OUTPUT
-        let (expected_output, output_size) = Self::formed_output_block_for(expected);
-        let expected_output_fields: [Fq; 64] = expected_output
-            .iter()
-            .map(|x| Fq::from(*x as u64))
-            .collect::<Vec<_>>()
-            .try_into()
-            .unwrap();
+        let (expected_output, output_size) = Self::formed_output_block_for(expected)?;
+        let expected_output_fields: [F; 64] = expected_output.map(|x| F::from(x as u64));
 
         // KEY
-        let key_size = key.len() / 2; // Amount of bytes
-        let key_bytes = hex::decode(key).expect("Invalid hex string");
+        let key_bytes = hex::decode(key)?;
+        let key_size = key_bytes.len();
         let key_values =
-            key_bytes.iter().map(|x| Value::known(Fq::from(*x as u64))).collect::<Vec<_>>();
+            key_bytes.iter().map(|x| Value::known(F::from(*x as u64))).collect::<Vec<_>>();
 
-        (input_values, input_size, key_values, key_size, expected_output_fields, output_size)
+        Ok((input_values, input_size, key_values, key_size, expected_output_fields, output_size))
     }
 
-    /// Convert the expected output of the circuit in byte blocks
-    pub fn formed_output_block_for(output: &String) -> ([u8; 64], usize) {
-        let output_block_size = output.len() / 2; // Amount of bytes
-        let output_bytes = hex::decode(output).expect("Invalid hex string");
-        (output_bytes.try_into().unwrap(), output_block_size)
+    /// Convert the expected output of the circuit in byte blocks. Outputs shorter than 64 bytes
+    /// are padded with zeros, which the circuit doesn't constrain. It fails if the output is not a
+    /// valid hex string or is longer than 64 bytes.
+    pub fn formed_output_block_for(output: &String) -> Result<([u8; 64], usize), Blake2bError> {
+        let mut output_bytes = hex::decode(output)?;
+        let output_block_size = output_bytes.len();
+        if output_block_size > 64 {
+            return Err(Blake2bError::InvalidOutputSize(output_block_size));
+        }
+        output_bytes.resize(64, 0);
+        Ok((output_bytes.try_into().unwrap(), output_block_size))
     }
 }
 
-/// Circuit runner methods for Real Prover
-impl CircuitRunner {
+/// Circuit runner methods for Real Prover. The proofs use a Blake2b transcript, so the field
+/// and the commitments of the scheme must be hashable into it.
+impl<F, CS> CircuitRunner<F, CS>
+where
+    F: WithSmallOrderMulGroup<3>
+        + Sampleable<Blake2bState>
+        + Hashable<Blake2bState>
+        + Hash
+        + Ord
+        + FromUniformBytes<64>,
+    CS: PolynomialCommitmentScheme<F>,
+    CS::Commitment: Hashable<Blake2bState>,
+{
     /// Preprocess inputs, synthesize, prove and verify the circuit using a real prover
     pub fn real_preprocess_inputs_synthesize_prove_and_verify(
         input: String,
         out: String,
         key: String,
-    ) -> Result<(), Error> {
-        let circuit_inputs = Self::prepare_parameters_for_test(&input, &key, &out);
+    ) -> Result<(), Blake2bError> {
+        let mut params_cache = ParamsCache::new();
+        Self::real_preprocess_inputs_synthesize_prove_and_verify_with_params(
+            input,
+            out,
+            key,
+            &mut params_cache,
+        )?;
+        Ok(())
+    }
 
-        let circuit: Blake2bCircuit<Fq> =
-            Self::create_circuit_for_packed_inputs(circuit_inputs.clone());
+    /// Same as [Self::real_preprocess_inputs_synthesize_prove_and_verify], with the parameters
+    /// of the k of the circuit taken from 'params_cache'. It returns the verified proof.
+    pub fn real_preprocess_inputs_synthesize_prove_and_verify_with_params(
+        input: String,
+        out: String,
+        key: String,
+        params_cache: &mut ParamsCache<F, CS>,
+    ) -> Result<ProofArtifacts, Blake2bError> {
+        let circuit_inputs = Self::prepare_parameters_for_test(&input, &key, &out)?;
 
-        let params = ParamsKZG::<Bls12>::unsafe_setup(17, &mut rand::thread_rng());
-        let vk: VerifyingKey<Fq, KZGCommitmentScheme<Bls12>> = Self::create_vk(&circuit, &params);
-        let pk: ProvingKey<Fq, KZGCommitmentScheme<Bls12>> = Self::create_pk(&circuit, vk);
-        let proof = Self::create_proof(&circuit_inputs.4, circuit, &params, &pk);
-        Self::verify(&circuit_inputs.4, &params, pk, &proof)
+        let circuit: Blake2bCircuit<F> =
+            Self::create_circuit_for_packed_inputs(circuit_inputs.clone())?;
+
+        let params = params_cache.params_for(&circuit);
+        let vk: VerifyingKey<F, CS> = Self::create_vk(&circuit, params)?;
+        let pk: ProvingKey<F, CS> = Self::create_pk(&circuit, vk)?;
+        let artifacts = Self::create_proof(&circuit_inputs.4, circuit, params, &pk)?;
+        Self::verify(&circuit_inputs.4, params, &pk, &artifacts.proof)?;
+        Ok(artifacts)
+    }
+
+    /// Generate the parameters of the commitment scheme for circuits of 2^k rows. For KZG these
+    /// come from an unsafe setup, only meant for testing.
+    pub fn create_params(k: u32) -> CS::Parameters {
+        CS::gen_params(k)
     }
 
-    /// Create the verifying key for the given circuit and parameters
+    /// Create the verifying key for the given circuit and parameters. The key is for circuits of
+    /// as many rows as the parameters support, so the proofs must be created with the same
+    /// parameters. It fails if the parameters are too small for the circuit (see
+    /// [Blake2bCircuit::k]).
     pub fn create_vk(
-        circuit: &Blake2bCircuit<Fq>,
-        params: &ParamsKZG<Bls12>,
-    ) -> VerifyingKey<Fq, KZGCommitmentScheme<Bls12>> {
-        keygen_vk_with_k(params, circuit, 17).expect("Verifying key should be created")
+        circuit: &Blake2bCircuit<F>,
+        params: &CS::Parameters,
+    ) -> Result<VerifyingKey<F, CS>, Blake2bError> {
+        if params.max_k() < circuit.k() {
+            return Err(Blake2bError::ParamsTooSmall(params.max_k(), circuit.k()));
+        }
+        Ok(keygen_vk_with_k(params, circuit, params.max_k())?)
     }
 
     /// Create the proving key for the given circuit and parameters
     pub fn create_pk(
-        circuit: &Blake2bCircuit<Fq>,
-        vk: VerifyingKey<Fq, KZGCommitmentScheme<Bls12>>,
-    ) -> ProvingKey<Fq, KZGCommitmentScheme<Bls12>> {
-        keygen_pk(vk.clone(), circuit).expect("Proving key should be created")
+        circuit: &Blake2bCircuit<F>,
+        vk: VerifyingKey<F, CS>,
+    ) -> Result<ProvingKey<F, CS>, Blake2bError> {
+        Ok(keygen_pk(vk, circuit)?)
     }
 
-    /// Create the proof for the given circuit and parameters
+    /// Create the proof for the given circuit and parameters, along with its public inputs
     pub fn create_proof(
-        expected_output_fields: &[Fq],
-        circuit: Blake2bCircuit<Fq>,
-        params: &ParamsKZG<Bls12>,
-        pk: &ProvingKey<Fq, KZGCommitmentScheme<Bls12>>,
-    ) -> Vec<u8> {
+        expected_output_fields: &[F],
+        circuit: Blake2bCircuit<F>,
+        params: &CS::Parameters,
+        pk: &ProvingKey<F, CS>,
+    ) -> Result<ProofArtifacts, Blake2bError> {
+        Self::create_proof_with_instances(&[expected_output_fields], circuit, params, pk)
+    }
+
+    /// Create the proof for a circuit in any [Blake2bInstanceMode]. 'instances' has the values of
+    /// every instance column, committed ones included, in the order given by the mode. Only the
+    /// public ones are part of the [ProofArtifacts].
+    pub fn create_proof_with_instances(
+        instances: &[&[F]],
+        circuit: Blake2bCircuit<F>,
+        params: &CS::Parameters,
+        pk: &ProvingKey<F, CS>,
+    ) -> Result<ProofArtifacts, Blake2bError> {
+        Self::create_proof_for_circuits(&[instances], vec![circuit], params, pk)
+    }
+
+    /// Create a single proof of many circuits with the same keys (see
+    /// [Blake2bCircuit::has_same_shape]), which shares the commitment and opening costs of the
+    /// proof among them. 'instances' has the values of the instance columns of every circuit, as
+    /// given to [Self::create_proof_with_instances], in the order of 'circuits'. The public ones
+    /// are part of the [ProofArtifacts] in the same order.
+    pub fn create_proof_for_circuits(
+        instances: &[&[&[F]]],
+        circuits: Vec<Blake2bCircuit<F>>,
+        params: &CS::Parameters,
+        pk: &ProvingKey<F, CS>,
+    ) -> Result<ProofArtifacts, Blake2bError> {
+        let first_circuit = circuits.first().ok_or(Error::InvalidInstances)?;
+        if let Some(index) =
+            circuits.iter().position(|circuit| !circuit.has_same_shape(first_circuit))
+        {
+            return Err(Blake2bError::MismatchedCircuitShape(index));
+        }
+        let nb_committed_instances = first_circuit.instance_mode().nb_committed_instances();
         let mut transcript: CircuitTranscript<Blake2bState> = CircuitTranscript::init();
         create_proof(
             params,
             pk,
-            &[circuit],
-            0,
-            &[&[expected_output_fields]],
+            &circuits,
+            nb_committed_instances,
+            instances,
             &mut transcript,
             rand::thread_rng(),
-        )
-        .expect("Proof generation should work");
-        transcript.finalize()
+        )?;
+        let public_instances: Vec<&[F]> = instances
+            .iter()
+            .flat_map(|columns| columns.iter().skip(nb_committed_instances).copied())
+            .collect();
+        Ok(ProofArtifacts::new(transcript.finalize(), &public_instances))
     }
 
-    /// Verify the proof for the given circuit and parameters
+    /// Commit to the values of a committed instance column, as the verifier gets them. The
+    /// commitment isn't blinded, so it only hides values that are hard to guess.
+    pub fn commit_to_instance(
+        vk: &VerifyingKey<F, CS>,
+        params: &CS::Parameters,
+        values: &[F],
+    ) -> CS::Commitment {
+        commit_to_instances::<F, CS>(params, vk.get_domain(), values)
+    }
+
+    /// Verify the proof for the given circuit and parameters. A proof that doesn't verify
+    /// results in an [Error::Opening] error.
     pub fn verify(
-        expected_output_fields: &[Fq],
-        params: &ParamsKZG<Bls12>,
-        pk: ProvingKey<Fq, KZGCommitmentScheme<Bls12>>,
+        expected_output_fields: &[F],
+        params: &CS::Parameters,
+        pk: &ProvingKey<F, CS>,
+        proof: &[u8],
+    ) -> Result<(), Blake2bError> {
+        Self::verify_with_vk(pk.get_vk(), params, expected_output_fields, proof)
+    }
+
+    /// Verify the proof using only the verifying key, as a verifier that never sees the proving
+    /// key would. A proof that doesn't verify results in an [Error::Opening] error.
+    pub fn verify_with_vk(
+        vk: &VerifyingKey<F, CS>,
+        params: &CS::Parameters,
+        expected_output_fields: &[F],
+        proof: &[u8],
+    ) -> Result<(), Blake2bError> {
+        Self::verify_with_committed_instances(vk, params, &[], &[expected_output_fields], proof)
+    }
+
+    /// Verify the proof of a circuit in any [Blake2bInstanceMode], given the commitments to its
+    /// committed instance columns (see [Self::commit_to_instance]) and the values of its public
+    /// ones. A proof that doesn't verify results in an [Error::Opening] error.
+    pub fn verify_with_committed_instances(
+        vk: &VerifyingKey<F, CS>,
+        params: &CS::Parameters,
+        committed_instances: &[CS::Commitment],
+        instances: &[&[F]],
+        proof: &[u8],
+    ) -> Result<(), Blake2bError> {
+        Self::verify_for_circuits(vk, params, &[committed_instances], &[instances], proof)
+    }
+
+    /// Verify a proof of many circuits created by [Self::create_proof_for_circuits], given the
+    /// commitments to the committed instance columns and the values of the public ones of every
+    /// circuit, in the order they were proven. A proof that doesn't verify results in an
+    /// [Error::Opening] error.
+    pub fn verify_for_circuits(
+        vk: &VerifyingKey<F, CS>,
+        params: &CS::Parameters,
+        committed_instances: &[&[CS::Commitment]],
+        instances: &[&[&[F]]],
         proof: &[u8],
-    ) -> Result<(), Error> {
+    ) -> Result<(), Blake2bError> {
         let mut transcript: CircuitTranscript<Blake2bState> =
             CircuitTranscript::init_from_bytes(proof);
 
-        assert!(prepare::<Fq, KZGCommitmentScheme<Bls12>, _>(
-            pk.get_vk(),
-            &[&[]],
-            &[&[expected_output_fields]],
-            &mut transcript,
-        )?
-        .verify(&params.verifier_params())
-        .is_ok());
+        prepare::<F, CS, _>(vk, committed_instances, instances, &mut transcript)?
+            .verify(&CS::get_verifier_params(params))
+            .map_err(|_| Error::Opening)?;
         Ok(())
     }
 }
+
+/// The parameters of the commitment scheme for every k requested so far. Generating them is the
+/// most expensive step of a setup, so circuits that fit in the same k share them.
+pub struct ParamsCache<F, CS: PolynomialCommitmentScheme<F>>
+where
+    F: PrimeField,
+{
+    params: BTreeMap<u32, CS::Parameters>,
+}
+
+impl<F: PrimeField, CS: PolynomialCommitmentScheme<F>> ParamsCache<F, CS> {
+    /// An empty cache
+    pub fn new() -> Self {
+        Self {
+            params: BTreeMap::new(),
+        }
+    }
+
+    /// The parameters for circuits of 2^k rows, generated with [CS::gen_params] the first time
+    /// they are requested.
+    pub fn params_for_k(&mut self, k: u32) -> &CS::Parameters {
+        self.params.entry(k).or_insert_with(|| CS::gen_params(k))
+    }
+
+    /// The parameters for the smallest k the circuit fits in, see [Blake2bCircuit::k].
+    pub fn params_for(&mut self, circuit: &Blake2bCircuit<F>) -> &CS::Parameters {
+        self.params_for_k(circuit.k())
+    }
+}
+
+impl<F: PrimeField, CS: PolynomialCommitmentScheme<F>> Default for ParamsCache<F, CS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: PrimeField, CS: PolynomialCommitmentScheme<F>> Debug for ParamsCache<F, CS> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParamsCache").field("k", &self.params.keys().collect::<Vec<_>>()).finish()
+    }
+}
+
+/// Circuit runner methods to verify many KZG proofs at once.
+impl<E> CircuitRunner<E::Fr, KZGCommitmentScheme<E>>
+where
+    E: MultiMillerLoop + Debug,
+    E::Fr: WithSmallOrderMulGroup<3>
+        + Sampleable<Blake2bState>
+        + Hashable<Blake2bState>
+        + Hash
+        + Ord
+        + FromUniformBytes<64>,
+    E::G1: Default + CurveExt<ScalarExt = E::Fr> + ProcessedSerdeObject + Hashable<Blake2bState>,
+    E::G1Affine: Default + CurveAffine<ScalarExt = E::Fr, CurveExt = E::G1>,
+{
+    /// Verify a batch of proofs of circuits with the same verifying key, given as pairs of the
+    /// public inputs and the proof, with a single pairing check. Each proof is prepared on its
+    /// own, and the resulting [DualMSM]s are folded into one with random scalars, so the pairing
+    /// check of the batch only passes if every proof would pass its own. An empty batch is
+    /// accepted.
+    ///
+    /// This is for circuits in [Blake2bInstanceMode::Public], whose only instance column holds
+    /// the public inputs. Proofs of circuits with committed instances are batched with
+    /// [Self::verify_batch_with_committed_instances].
+    ///
+    /// A batch with an invalid proof results in an [Error::Opening] error that doesn't tell which
+    /// proof is invalid. Use [Self::verify_with_vk] on each proof to find it.
+    pub fn verify_batch(
+        vk: &VerifyingKey<E::Fr, KZGCommitmentScheme<E>>,
+        params: &ParamsKZG<E>,
+        proofs: &[(&[E::Fr], &[u8])],
+    ) -> Result<(), Blake2bError> {
+        let instances: Vec<[&[E::Fr]; 1]> =
+            proofs.iter().map(|(public_inputs, _)| [*public_inputs]).collect();
+        let instances: Vec<&[&[E::Fr]]> = instances.iter().map(|columns| &columns[..]).collect();
+        let proofs: Vec<&[u8]> = proofs.iter().map(|(_, proof)| *proof).collect();
+        Self::verify_batch_with_committed_instances(
+            vk,
+            params,
+            &vec![&[][..]; proofs.len()],
+            &instances,
+            &proofs,
+        )
+    }
+
+    /// Verify a batch of proofs of circuits in any [Blake2bInstanceMode] with the same verifying
+    /// key, as [Self::verify_batch] does. For every proof, in the order of 'proofs', it takes the
+    /// commitments to its committed instance columns (see [Self::commit_to_instance]) and the
+    /// values of its public ones, as [Self::verify_with_committed_instances] does. Giving the
+    /// instances of a different number of proofs results in an [Error::InvalidInstances] error.
+    ///
+    /// A batch with an invalid proof results in an [Error::Opening] error that doesn't tell which
+    /// proof is invalid. Use [Self::verify_with_committed_instances] on each proof to find it.
+    pub fn verify_batch_with_committed_instances(
+        vk: &VerifyingKey<E::Fr, KZGCommitmentScheme<E>>,
+        params: &ParamsKZG<E>,
+        committed_instances: &[&[E::G1]],
+        instances: &[&[&[E::Fr]]],
+        proofs: &[&[u8]],
+    ) -> Result<(), Blake2bError> {
+        if committed_instances.len() != proofs.len() || instances.len() != proofs.len() {
+            return Err(Error::InvalidInstances.into());
+        }
+        let mut rng = rand::thread_rng();
+        let mut accumulator = DualMSM::<E>::init();
+        for ((committed_instances, instances), proof) in
+            committed_instances.iter().zip(instances).zip(proofs)
+        {
+            let mut transcript: CircuitTranscript<Blake2bState> =
+                CircuitTranscript::init_from_bytes(proof);
+            let mut guard = prepare::<E::Fr, KZGCommitmentScheme<E>, _>(
+                vk,
+                &[committed_instances],
+                &[instances],
+                &mut transcript,
+            )?;
+            guard.scale(E::Fr::random(&mut rng));
+            accumulator.add_msm(guard);
+        }
+        if proofs.is_empty() || accumulator.check(&params.verifier_params()) {
+            Ok(())
+        } else {
+            Err(Error::Opening.into())
+        }
+    }
+}
+
+/// Circuit runner methods to store and load the KZG params, in one of the serialization formats of
+/// midnight-proofs.
+impl<E> CircuitRunner<E::Fr, KZGCommitmentScheme<E>>
+where
+    E: Engine + Debug,
+    E::G1: Curve + ProcessedSerdeObject,
+    E::G2: Curve + ProcessedSerdeObject,
+{
+    /// Serialize the KZG params
+    pub fn params_to_bytes(
+        params: &ParamsKZG<E>,
+        format: SerdeFormat,
+    ) -> Result<Vec<u8>, Blake2bError> {
+        let mut bytes = Vec::new();
+        params.write_custom(&mut bytes, format)?;
+        Ok(bytes)
+    }
+
+    /// Deserialize the KZG params
+    pub fn params_from_bytes(
+        mut bytes: &[u8],
+        format: SerdeFormat,
+    ) -> Result<ParamsKZG<E>, Blake2bError> {
+        Ok(ParamsKZG::read_custom(&mut bytes, format)?)
+    }
+
+    /// Write the KZG params to the file at 'path'
+    pub fn write_params(
+        params: &ParamsKZG<E>,
+        path: impl AsRef<Path>,
+        format: SerdeFormat,
+    ) -> Result<(), Blake2bError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        params.write_custom(&mut writer, format)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Read the KZG params from the file at 'path'
+    pub fn read_params(
+        path: impl AsRef<Path>,
+        format: SerdeFormat,
+    ) -> Result<ParamsKZG<E>, Blake2bError> {
+        Ok(ParamsKZG::read_custom(&mut BufReader::new(File::open(path)?), format)?)
+    }
+}
+
+/// Circuit runner methods to store and load the verifying keys and proving keys, in one of the
+/// serialization formats of midnight-proofs.
+impl<F, CS> CircuitRunner<F, CS>
+where
+    F: WithSmallOrderMulGroup<3> + FromUniformBytes<64> + SerdeObject,
+    CS: PolynomialCommitmentScheme<F>,
+{
+    /// Serialize the verifying key
+    pub fn vk_to_bytes(vk: &VerifyingKey<F, CS>, format: SerdeFormat) -> Vec<u8> {
+        vk.to_bytes(format)
+    }
+
+    /// Deserialize a verifying key of a [Blake2bCircuit] with the given instance mode, which must
+    /// be the one the key was generated for ([Blake2bInstanceMode::Public] for [Blake2bCircuit::new])
+    pub fn vk_from_bytes(
+        bytes: &[u8],
+        format: SerdeFormat,
+        instance_mode: Blake2bInstanceMode,
+    ) -> Result<VerifyingKey<F, CS>, Blake2bError> {
+        Ok(VerifyingKey::from_bytes::<Blake2bCircuit<F>>(bytes, format, instance_mode)?)
+    }
+
+    /// Write the verifying key to the file at 'path'
+    pub fn write_vk(
+        vk: &VerifyingKey<F, CS>,
+        path: impl AsRef<Path>,
+        format: SerdeFormat,
+    ) -> Result<(), Blake2bError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        vk.write(&mut writer, format)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Read a verifying key of a [Blake2bCircuit] with the given instance mode from the file at
+    /// 'path'
+    pub fn read_vk(
+        path: impl AsRef<Path>,
+        format: SerdeFormat,
+        instance_mode: Blake2bInstanceMode,
+    ) -> Result<VerifyingKey<F, CS>, Blake2bError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Ok(VerifyingKey::read::<_, Blake2bCircuit<F>>(&mut reader, format, instance_mode)?)
+    }
+
+    /// Serialize the proving key
+    pub fn pk_to_bytes(pk: &ProvingKey<F, CS>, format: SerdeFormat) -> Vec<u8> {
+        pk.to_bytes(format)
+    }
+
+    /// Deserialize a proving key of a [Blake2bCircuit] with the given instance mode, which must
+    /// be the one the key was generated for ([Blake2bInstanceMode::Public] for [Blake2bCircuit::new])
+    pub fn pk_from_bytes(
+        bytes: &[u8],
+        format: SerdeFormat,
+        instance_mode: Blake2bInstanceMode,
+    ) -> Result<ProvingKey<F, CS>, Blake2bError> {
+        Ok(ProvingKey::from_bytes::<Blake2bCircuit<F>>(bytes, format, instance_mode)?)
+    }
+
+    /// Write the proving key to the file at 'path'
+    pub fn write_pk(
+        pk: &ProvingKey<F, CS>,
+        path: impl AsRef<Path>,
+        format: SerdeFormat,
+    ) -> Result<(), Blake2bError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        pk.write(&mut writer, format)?;
+        Ok(writer.flush()?)
+    }
+
+    /// Read a proving key of a [Blake2bCircuit] with the given instance mode from the file at
+    /// 'path'
+    pub fn read_pk(
+        path: impl AsRef<Path>,
+        format: SerdeFormat,
+        instance_mode: Blake2bInstanceMode,
+    ) -> Result<ProvingKey<F, CS>, Blake2bError> {
+        let mut reader = BufReader::new(File::open(path)?);
+        Ok(ProvingKey::read::<_, Blake2bCircuit<F>>(&mut reader, format, instance_mode)?)
+    }
+}
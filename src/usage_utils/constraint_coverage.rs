@@ -0,0 +1,190 @@
+//! An under-constrained cell detector over the layout of the example [Blake2bCircuit]. It walks
+//! the gates and lookups of the constraint system together with the selectors and copy
+//! constraints recorded during synthesis, and tells which constraints touch every advice cell the
+//! chip assigns. A cell that no constraint touches can hold any value in a proof.
+
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use crate::usage_utils::layout::{recorded_cells_of, AdviceCell, RecordedCells};
+use ff::PrimeField;
+use midnight_curves::bls12_381::Fq;
+use midnight_proofs::plonk::{Any, Expression};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The constraints that touch an advice cell.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellCoverage {
+    /// Names of the gates that query the cell, with the name of the constraint when it has one.
+    pub gates: BTreeSet<String>,
+    /// Names of the lookups whose inputs query the cell.
+    pub lookups: BTreeSet<String>,
+    /// Number of copy constraints of the cell.
+    pub copies: usize,
+}
+
+impl CellCoverage {
+    /// Whether no gate, lookup or copy constraint touches the cell.
+    pub fn is_unconstrained(&self) -> bool {
+        self.gates.is_empty() && self.lookups.is_empty() && self.copies == 0
+    }
+
+    fn extend(&mut self, other: &CellCoverage) {
+        self.gates.extend(other.gates.iter().cloned());
+        self.lookups.extend(other.lookups.iter().cloned());
+        self.copies += other.copies;
+    }
+}
+
+/// The coverage of every advice cell of a circuit.
+#[derive(Clone, Debug)]
+pub struct ConstraintCoverage {
+    /// Every advice cell with the constraints that touch it, in assignment order.
+    pub cells: Vec<(AdviceCell, CellCoverage)>,
+}
+
+impl ConstraintCoverage {
+    /// The cells no constraint touches.
+    pub fn unconstrained_cells(&self) -> Vec<&AdviceCell> {
+        let cells = self.cells.iter();
+        cells.filter(|(_, coverage)| coverage.is_unconstrained()).map(|(cell, _)| cell).collect()
+    }
+
+    /// The union of the constraints over every kind of cell, i.e. the cells with the same
+    /// (annotation, column).
+    pub fn by_kind(&self) -> BTreeMap<(String, usize), CellCoverage> {
+        let mut kinds: BTreeMap<(String, usize), CellCoverage> = BTreeMap::new();
+        for (cell, coverage) in &self.cells {
+            kinds.entry((cell.annotation.clone(), cell.column)).or_default().extend(coverage);
+        }
+        kinds
+    }
+
+    /// The coverage of every kind of cell followed by the unconstrained cells, one per line.
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        for ((annotation, column), kind) in self.by_kind() {
+            table.push_str(&format!(
+                "'{annotation}' at column {column}: gates {:?}, lookups {:?}, {} copies\n",
+                kind.gates, kind.lookups, kind.copies
+            ));
+        }
+        for cell in self.unconstrained_cells() {
+            table.push_str(&format!(
+                "Unconstrained cell: '{}' in region '{}' at column {}, row {}\n",
+                cell.annotation, cell.region, cell.column, cell.row
+            ));
+        }
+        table
+    }
+}
+
+/// The constraint coverage of the circuit of [Blake2bCircuit::for_sizes] for the given sizes.
+pub fn constraint_coverage(
+    input_len: usize,
+    key_len: usize,
+    output_len: usize,
+) -> Result<ConstraintCoverage, Blake2bError> {
+    let circuit = Blake2bCircuit::<Fq>::for_sizes(input_len, key_len, output_len)?;
+    constraint_coverage_of(&circuit)
+}
+
+/// The constraint coverage of 'circuit', with its instance mode, digest layout and public input
+/// bytes. A gate or lookup is taken to be active on the rows where all the selectors it queries
+/// are enabled, or on every row if it queries none, and it touches the advice cells it queries
+/// relative to those rows.
+pub fn constraint_coverage_of<F: PrimeField>(
+    circuit: &Blake2bCircuit<F>,
+) -> Result<ConstraintCoverage, Blake2bError> {
+    let RecordedCells {
+        meta,
+        advice_cells,
+        enabled_selectors,
+        copies,
+    } = recorded_cells_of(circuit)?;
+
+    let mut enabled_rows: HashMap<usize, BTreeSet<usize>> = HashMap::new();
+    for (selector, row) in enabled_selectors {
+        enabled_rows.entry(selector).or_default().insert(row);
+    }
+    let all_rows: BTreeSet<usize> = advice_cells.iter().map(|cell| cell.row).collect();
+    let active_rows = |selectors: &BTreeSet<usize>| -> BTreeSet<usize> {
+        selectors.iter().fold(all_rows.clone(), |rows, selector| {
+            let enabled = enabled_rows.get(selector).cloned().unwrap_or_default();
+            rows.intersection(&enabled).cloned().collect()
+        })
+    };
+    let touched_cells = |expressions: &[Expression<F>]| -> Vec<(usize, usize)> {
+        let (mut selectors, mut advice) = (BTreeSet::new(), BTreeSet::new());
+        for expression in expressions {
+            queries_of(expression, &mut selectors, &mut advice);
+        }
+        let rows = active_rows(&selectors);
+        rows.iter()
+            .flat_map(|row| {
+                advice.iter().filter_map(move |(column, rotation)| {
+                    let row = *row as i64 + *rotation as i64;
+                    (row >= 0).then_some((*column, row as usize))
+                })
+            })
+            .collect()
+    };
+
+    let mut coverage: HashMap<(usize, usize), CellCoverage> = HashMap::new();
+    for gate in meta.gates() {
+        for (index, polynomial) in gate.polynomials().iter().enumerate() {
+            let name = match gate.constraint_name(index) {
+                "" => gate.name().to_string(),
+                constraint => format!("{}: {constraint}", gate.name()),
+            };
+            for cell in touched_cells(std::slice::from_ref(polynomial)) {
+                coverage.entry(cell).or_default().gates.insert(name.clone());
+            }
+        }
+    }
+    for lookup in meta.lookups() {
+        for cell in touched_cells(lookup.input_expressions()) {
+            coverage.entry(cell).or_default().lookups.insert(lookup.name().to_string());
+        }
+    }
+    for (left, right) in &copies {
+        for (column, row) in [left, right] {
+            if matches!(column.column_type(), Any::Advice(_)) {
+                coverage.entry((column.index(), *row)).or_default().copies += 1;
+            }
+        }
+    }
+
+    let cells = advice_cells
+        .into_iter()
+        .map(|cell| {
+            let cell_coverage = coverage.get(&(cell.column, cell.row)).cloned().unwrap_or_default();
+            (cell, cell_coverage)
+        })
+        .collect();
+    Ok(ConstraintCoverage { cells })
+}
+
+/// The selectors and the (advice column, rotation) queries of an expression.
+fn queries_of<F: PrimeField>(
+    expression: &Expression<F>,
+    selectors: &mut BTreeSet<usize>,
+    advice: &mut BTreeSet<(usize, i32)>,
+) {
+    match expression {
+        Expression::Selector(selector) => {
+            selectors.insert(selector.index());
+        }
+        Expression::Advice(query) => {
+            advice.insert((query.column_index(), query.rotation().0));
+        }
+        Expression::Negated(a) | Expression::Scaled(a, _) => queries_of(a, selectors, advice),
+        Expression::Sum(a, b) | Expression::Product(a, b) => {
+            queries_of(a, selectors, advice);
+            queries_of(b, selectors, advice);
+        }
+        Expression::Constant(_)
+        | Expression::Fixed(_)
+        | Expression::Instance(_)
+        | Expression::Challenge(_) => {}
+    }
+}
@@ -0,0 +1,156 @@
+//! Cost report of the example [Blake2bCircuit], used to plan the capacity needed to prove hashes
+//! of a given size.
+
+use crate::blake2b::blake2b_chip::Blake2bChip;
+use crate::blake2b::utils::{get_total_blocks_count, BLAKE2B_BLOCK_SIZE};
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use crate::usage_utils::layout::circuit_layout_of;
+use ff::PrimeField;
+use midnight_curves::bls12_381::{Fq, G1Affine};
+use midnight_curves::pairing::group::GroupEncoding;
+use midnight_proofs::dev::cost_model::circuit_model;
+use midnight_proofs::plonk::{Circuit, ConstraintSystem, Expression};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Size in bytes of a compressed G1 point of the curve, i.e. of a commitment.
+const COMMITMENT_SIZE: usize = size_of::<<G1Affine as GroupEncoding>::Repr>();
+/// Size in bytes of a scalar of the curve.
+const SCALAR_SIZE: usize = size_of::<<Fq as PrimeField>::Repr>();
+
+/// The cost of proving a hash with the [Blake2bCircuit].
+#[derive(Clone, Debug, Serialize)]
+pub struct CostReport {
+    /// Size of the input, in bytes.
+    pub input_len: usize,
+    /// Size of the key, in bytes.
+    pub key_len: usize,
+    /// Size of the output, in bytes.
+    pub output_len: usize,
+    /// Number of blocks compressed by the hash.
+    pub blocks: usize,
+    /// The circuit fits in 2^k rows.
+    pub k: u32,
+    /// Number of advice rows used by the circuit.
+    pub advice_rows: usize,
+    /// Number of advice rows added by each block.
+    pub advice_rows_per_block: usize,
+    /// Number of rows of the lookup tables.
+    pub table_rows: usize,
+    /// Number of rows reserved for blinding, which can't be used by the circuit.
+    pub unusable_rows: usize,
+    /// Number of advice columns.
+    pub advice_columns: usize,
+    /// Number of fixed columns, including selectors, lookup tables and the commitments of the
+    /// permutation argument.
+    pub fixed_columns: usize,
+    /// Number of instance columns.
+    pub instance_columns: usize,
+    /// Number of columns with equality enabled.
+    pub permutation_columns: usize,
+    /// Number of lookup arguments of each kind: "range" checks of the limbs and "xor"s.
+    pub lookup_arguments: BTreeMap<String, usize>,
+    /// Number of rows where the lookups of each kind are enabled.
+    pub lookup_rows: BTreeMap<String, usize>,
+    /// Maximum degree of the constraints.
+    pub max_degree: usize,
+    /// Expected size of the proof, in bytes.
+    pub proof_size: usize,
+}
+
+impl CostReport {
+    /// The report as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, Blake2bError> {
+        serde_json::to_string_pretty(self).map_err(|error| Blake2bError::Io(error.into()))
+    }
+}
+
+/// Computes the cost of the circuit of [Blake2bCircuit::for_sizes] for the given sizes.
+pub fn cost_report(
+    input_len: usize,
+    key_len: usize,
+    output_len: usize,
+) -> Result<CostReport, Blake2bError> {
+    let circuit = Blake2bCircuit::<Fq>::for_sizes(input_len, key_len, output_len)?;
+    let model = circuit_model::<Fq, COMMITMENT_SIZE, SCALAR_SIZE>(&circuit);
+
+    let mut meta = ConstraintSystem::<Fq>::default();
+    let (config, ..) = Blake2bCircuit::<Fq>::configure(&mut meta);
+    let selector_names: BTreeMap<usize, &'static str> =
+        config.named_selectors().iter().map(|(name, selector)| (selector.index(), *name)).collect();
+    let mut lookup_arguments = BTreeMap::new();
+    let mut lookup_selectors: BTreeMap<String, BTreeSet<&'static str>> = BTreeMap::new();
+    for argument in meta.lookups() {
+        let kind = lookup_kind(argument.name());
+        *lookup_arguments.entry(kind.clone()).or_insert(0) += 1;
+        let selectors = argument.input_expressions().iter().flat_map(selector_indices);
+        lookup_selectors
+            .entry(kind)
+            .or_default()
+            .extend(selectors.map(|index| selector_names[&index]));
+    }
+
+    let layout = circuit_layout_of(&circuit)?;
+    let lookup_rows = lookup_selectors
+        .into_iter()
+        .map(|(kind, selectors)| {
+            let rows = layout
+                .rows
+                .iter()
+                .filter(|row| row.selectors.iter().any(|selector| selectors.contains(selector)))
+                .count();
+            (kind, rows)
+        })
+        .collect();
+
+    let input_blocks = input_len.div_ceil(BLAKE2B_BLOCK_SIZE);
+    Ok(CostReport {
+        input_len,
+        key_len,
+        output_len,
+        blocks: get_total_blocks_count(input_blocks, input_len == 0, key_len == 0),
+        k: model.k,
+        advice_rows: model.rows,
+        advice_rows_per_block: Blake2bChip::<Fq>::rows_per_block(),
+        table_rows: model.table_rows,
+        unusable_rows: model.nb_unusable_rows,
+        advice_columns: model.advice_columns,
+        fixed_columns: model.fixed_columns,
+        instance_columns: meta.num_instance_columns(),
+        permutation_columns: model.permutations,
+        lookup_arguments,
+        lookup_rows,
+        max_degree: model.max_deg,
+        proof_size: model.size,
+    })
+}
+
+/// The kind of a lookup of the chip, from the name the chip gives it. The range checks are told
+/// apart from the xors by their name and not by their table, since they may look up the xor table
+/// (see [RangeCheckTable](crate::blake2b::blake2b_chip::RangeCheckTable)).
+fn lookup_kind(name: &str) -> String {
+    if name.starts_with("xor lookup") {
+        "xor".to_string()
+    } else if name.starts_with("lookup limb") {
+        "range".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// The indices of the selectors that enable 'expression'.
+fn selector_indices(expression: &Expression<Fq>) -> Vec<usize> {
+    expression.evaluate(
+        &|_| vec![],
+        &|selector| vec![selector.index()],
+        &|_| vec![],
+        &|_| vec![],
+        &|_| vec![],
+        &|_| vec![],
+        &|a| a,
+        &|a, b| [a, b].concat(),
+        &|a, b| [a, b].concat(),
+        &|a, _| a,
+    )
+}
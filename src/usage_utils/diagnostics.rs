@@ -0,0 +1,352 @@
+//! Diagnostics of the failures of the [MockProver] on the example [Blake2bCircuit]. The failures of
+//! midnight-proofs point at regions, offsets and columns; this module translates them into the
+//! block, round, mix and operation of the hash whose rows failed, e.g. "block 2, round 5, G3
+//! (b = b ^ c): xor lookup failed on limb 4". The rows of every operation are taken from the
+//! [CircuitLayout] of the circuit being diagnosed, as labelled by the chip while synthesizing it.
+
+use crate::error::Blake2bError;
+use ff::{Field, FromUniformBytes, PrimeField};
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use crate::usage_utils::layout::{circuit_layout_of, CircuitLayout};
+use midnight_curves::bls12_381::Fq;
+use midnight_proofs::dev::{metadata, FailureLocation, MockProver, VerifyFailure};
+use midnight_proofs::plonk::{Any, Circuit, ConstraintSystem, Expression};
+use std::fmt;
+
+/// A failure of the [MockProver], in terms of the hash.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FailureDiagnostic {
+    /// The row of the failure, when it is known.
+    pub row: Option<usize>,
+    /// The region of the failure, when it is inside one.
+    pub region: Option<String>,
+    /// The block being compressed, for failures in the region of the chip. The key, if any, is
+    /// block 0.
+    pub block: Option<usize>,
+    /// The round of the compression, for failures in a mix.
+    pub round: Option<usize>,
+    /// The index of the mix in its round, for failures in a mix.
+    pub mix: Option<usize>,
+    /// The operation of the chip that failed, or empty outside the region of the chip.
+    pub operation: String,
+    /// What failed, e.g. "xor lookup failed on limb 4".
+    pub description: String,
+}
+
+impl fmt::Display for FailureDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut location = vec![];
+        if let Some(block) = self.block {
+            location.push(format!("block {block}"));
+        }
+        if let Some(round) = self.round {
+            location.push(format!("round {round}"));
+        }
+        if let Some(mix) = self.mix {
+            location.push(format!("G{mix}"));
+        }
+        if location.is_empty() {
+            if let Some(region) = &self.region {
+                location.push(format!("region '{region}'"));
+            }
+            if let Some(row) = self.row {
+                location.push(format!("row {row}"));
+            }
+        }
+        let location =
+            if location.is_empty() { "circuit".to_string() } else { location.join(", ") };
+        if self.operation.is_empty() {
+            write!(f, "{location}: {}", self.description)
+        } else {
+            write!(f, "{location} ({}): {}", self.operation, self.description)
+        }
+    }
+}
+
+/// A gate or lookup of the circuit. Its operation is the one of the last row it queries, since
+/// the gates of additions and xors are enabled on the row of their first operand, which may be
+/// the result of the previous operation.
+#[derive(Clone, Debug)]
+struct Check {
+    description: String,
+    rows_after: usize,
+}
+
+/// Translates the failures of the [MockProver] on a [Blake2bCircuit] of some input, key and output
+/// sizes into [FailureDiagnostic]s.
+#[derive(Clone, Debug)]
+pub struct Blake2bDiagnostics {
+    layout: CircuitLayout,
+    /// The constraints of every gate, as the [MockProver] reports them.
+    constraints: Vec<(metadata::Constraint, Check)>,
+    gates: Vec<metadata::Gate>,
+    lookups: Vec<Check>,
+    /// Names of the advice columns of the chip.
+    column_names: Vec<(usize, String)>,
+}
+
+impl Blake2bDiagnostics {
+    /// Records the layout of the circuit of [Blake2bCircuit::for_sizes] for the given sizes, which
+    /// has the default instance mode and digest layout and no public input bytes.
+    pub fn new(input_len: usize, key_len: usize, output_len: usize) -> Result<Self, Blake2bError> {
+        let circuit = Blake2bCircuit::<Fq>::for_sizes(input_len, key_len, output_len)?;
+        Self::for_circuit(&circuit)
+    }
+
+    /// Records the layout of 'circuit', which is the circuit whose failures are diagnosed. Its
+    /// instance mode, digest layout and public input bytes change the regions of the circuit, so
+    /// the failures of a circuit can only be diagnosed with its own layout.
+    pub fn for_circuit<F: PrimeField>(circuit: &Blake2bCircuit<F>) -> Result<Self, Blake2bError> {
+        let layout = circuit_layout_of(circuit)?;
+        let mut meta = ConstraintSystem::<F>::default();
+        let config = Blake2bCircuit::<F>::configure_with_params(&mut meta, circuit.params());
+        let column_names: Vec<(usize, String)> = config
+            .0
+            .limbs
+            .iter()
+            .enumerate()
+            .map(|(i, limb)| (limb.index(), format!("limb {i}")))
+            .collect();
+
+        let mut constraints = vec![];
+        let mut gates = vec![];
+        for (gate_index, gate) in meta.gates().iter().enumerate() {
+            let metadata_gate: metadata::Gate = (gate_index, gate.name()).into();
+            for (poly_index, poly) in gate.polynomials().iter().enumerate() {
+                let constraint: metadata::Constraint =
+                    (metadata_gate.clone(), poly_index, gate.constraint_name(poly_index)).into();
+                let check = Check {
+                    description: format!("gate '{}' is not satisfied", gate.name()),
+                    rows_after: last_queried_row(std::slice::from_ref(poly)),
+                };
+                constraints.push((constraint, check));
+            }
+            gates.push(metadata_gate);
+        }
+
+        let lookups = meta
+            .lookups()
+            .iter()
+            .map(|lookup| {
+                let kind =
+                    if lookup.name().starts_with("xor") { "xor lookup" } else { "range check" };
+                let limb = queried_advice_columns(lookup.input_expressions())
+                    .into_iter()
+                    .find_map(|column| column_name(&column_names, column));
+                let description = match limb {
+                    Some(limb) => format!("{kind} failed on {limb}"),
+                    None => format!("{kind} '{}' failed", lookup.name()),
+                };
+                Check {
+                    description,
+                    rows_after: last_queried_row(lookup.input_expressions()),
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            layout,
+            constraints,
+            gates,
+            lookups,
+            column_names,
+        })
+    }
+
+    /// The diagnostics of the failures of 'prover', or nothing if it is satisfied. The layout of
+    /// the circuit doesn't depend on the field, so 'prover' may run on any of them.
+    pub fn verify<F: PrimeField + FromUniformBytes<64> + Ord>(
+        &self,
+        prover: &MockProver<F>,
+    ) -> Result<(), Vec<FailureDiagnostic>> {
+        prover.verify().map_err(|failures| self.diagnose_all(&failures))
+    }
+
+    /// The diagnostic of every failure, in order.
+    pub fn diagnose_all(&self, failures: &[VerifyFailure]) -> Vec<FailureDiagnostic> {
+        failures.iter().map(|failure| self.diagnose(failure)).collect()
+    }
+
+    /// The diagnostic of a single failure.
+    pub fn diagnose(&self, failure: &VerifyFailure) -> FailureDiagnostic {
+        match failure {
+            VerifyFailure::CellNotAssigned {
+                gate,
+                region,
+                gate_offset,
+                column,
+                offset,
+            } => {
+                let gate_index = self.gates.iter().position(|g| g == gate);
+                let description = format!(
+                    "{} uses the unassigned cell of {} at offset {offset}",
+                    gate_index.map_or("a gate".to_string(), |i| format!("gate {i}")),
+                    self.describe_column((*column).into()),
+                );
+                self.diagnostic_in_region(region, *gate_offset, 0, description)
+            }
+            VerifyFailure::InstanceCellNotAssigned {
+                region,
+                gate_offset,
+                row,
+                ..
+            } => {
+                let description = format!("the instance cell at row {row} is not assigned");
+                self.diagnostic_in_region(region, *gate_offset, 0, description)
+            }
+            VerifyFailure::ConstraintNotSatisfied {
+                constraint,
+                location,
+                ..
+            } => {
+                let check = self.constraint_check(constraint);
+                self.diagnostic_at(location, check.rows_after, check.description)
+            }
+            VerifyFailure::ConstraintPoisoned { constraint } => {
+                let check = self.constraint_check(constraint);
+                self.diagnostic(None, 0, format!("{} on an unusable row", check.description))
+            }
+            VerifyFailure::Lookup {
+                name,
+                lookup_index,
+                location,
+            } => {
+                let check = self.lookups.get(*lookup_index).cloned().unwrap_or_else(|| Check {
+                    description: format!("lookup '{name}' failed"),
+                    rows_after: 0,
+                });
+                self.diagnostic_at(location, check.rows_after, check.description)
+            }
+            VerifyFailure::Permutation { column, location } => {
+                let description =
+                    format!("copy constraint failed on {}", self.describe_column(*column));
+                self.diagnostic_at(location, 0, description)
+            }
+        }
+    }
+
+    fn constraint_check(&self, constraint: &metadata::Constraint) -> Check {
+        self.constraints
+            .iter()
+            .find(|(c, _)| c == constraint)
+            .map(|(_, check)| check.clone())
+            .unwrap_or_else(|| Check {
+                description: format!("{constraint} is not satisfied"),
+                rows_after: 0,
+            })
+    }
+
+    fn describe_column(&self, column: metadata::Column) -> String {
+        match column.column_type() {
+            Any::Advice(_) => column_name(&self.column_names, column.index())
+                .unwrap_or_else(|| format!("advice column {}", column.index())),
+            Any::Fixed => format!("fixed column {}", column.index()),
+            Any::Instance => format!("instance column {}", column.index()),
+        }
+    }
+
+    fn diagnostic_at(
+        &self,
+        location: &FailureLocation,
+        rows_after: usize,
+        description: String,
+    ) -> FailureDiagnostic {
+        match location {
+            FailureLocation::InRegion { region, offset } => {
+                self.diagnostic_in_region(region, *offset, rows_after, description)
+            }
+            FailureLocation::OutsideRegion { row } => {
+                self.diagnostic(Some(*row), rows_after, description)
+            }
+        }
+    }
+
+    fn diagnostic_in_region(
+        &self,
+        region: &metadata::Region,
+        offset: usize,
+        rows_after: usize,
+        description: String,
+    ) -> FailureDiagnostic {
+        let recorded = self.layout.regions.iter().enumerate().find(|(index, recorded)| {
+            metadata::Region::from((*index, recorded.name.as_str())) == *region
+        });
+        let row = recorded.and_then(|(_, recorded)| recorded.start).map(|start| start + offset);
+        let mut diagnostic = self.diagnostic(row, rows_after, description);
+        if let Some((_, recorded)) = recorded {
+            diagnostic.region = Some(recorded.name.clone());
+        }
+        diagnostic
+    }
+
+    /// The diagnostic of a failure of a check that starts at 'row'. The operation is the one of
+    /// the last row queried by the check.
+    fn diagnostic(
+        &self,
+        row: Option<usize>,
+        rows_after: usize,
+        description: String,
+    ) -> FailureDiagnostic {
+        let layout_row = row.and_then(|row| {
+            self.layout
+                .row(row + rows_after)
+                .filter(|layout_row| !layout_row.operation.is_empty())
+                .or_else(|| self.layout.row(row))
+        });
+        FailureDiagnostic {
+            row,
+            region: layout_row.and_then(|layout_row| layout_row.regions.first().cloned()),
+            block: layout_row.and_then(|layout_row| layout_row.block),
+            round: layout_row.and_then(|layout_row| layout_row.round),
+            mix: layout_row.and_then(|layout_row| layout_row.mix),
+            operation: layout_row
+                .map(|layout_row| layout_row.operation.clone())
+                .unwrap_or_default(),
+            description,
+        }
+    }
+}
+
+fn column_name(column_names: &[(usize, String)], index: usize) -> Option<String> {
+    column_names.iter().find(|(column, _)| *column == index).map(|(_, name)| name.clone())
+}
+
+/// The (column, rotation) of every advice query of the expressions.
+fn advice_queries<F: Field>(expressions: &[Expression<F>]) -> Vec<(usize, i32)> {
+    expressions
+        .iter()
+        .flat_map(|expression| {
+            expression.evaluate(
+                &|_| vec![],
+                &|_| vec![],
+                &|_| vec![],
+                &|query| vec![(query.column_index(), query.rotation().0)],
+                &|_| vec![],
+                &|_| vec![],
+                &|a| a,
+                &|mut a, mut b| {
+                    a.append(&mut b);
+                    a
+                },
+                &|mut a, mut b| {
+                    a.append(&mut b);
+                    a
+                },
+                &|a, _| a,
+            )
+        })
+        .collect()
+}
+
+fn queried_advice_columns<F: Field>(expressions: &[Expression<F>]) -> Vec<usize> {
+    advice_queries(expressions).into_iter().map(|(column, _)| column).collect()
+}
+
+/// How many rows after the one it is enabled on the expressions query.
+fn last_queried_row<F: Field>(expressions: &[Expression<F>]) -> usize {
+    advice_queries(expressions)
+        .into_iter()
+        .map(|(_, rotation)| rotation.max(0) as usize)
+        .max()
+        .unwrap_or(0)
+}
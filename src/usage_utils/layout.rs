@@ -0,0 +1,443 @@
+//! Layout of the example [Blake2bCircuit], as a table with one line per row. It shows the regions,
+//! the assigned advice cells and the enabled selectors of every row, and labels the rows of the
+//! region of the chip with the block, round, mix and operation they belong to, as the chip records
+//! them while it assigns its rows. Many operations of the chip rely on the rows next to them, e.g.
+//! an addition that reuses the result of the previous operation as its first operand enables
+//! 'q_add' on the row of that result, and this table makes such adjacencies visible.
+//!
+//! It is a dev tool, only public with the dev-graph feature. Without it, the cost report is the
+//! only user of the layout, so most of it is unused.
+#![cfg_attr(not(any(test, feature = "dev-graph")), allow(dead_code))]
+
+use crate::blake2b::blake2b_chip::Blake2bChip;
+use crate::blake2b::row_labels::RowLabels;
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::{Blake2bCircuit, Blake2bInstanceMode};
+use ff::{Field, PrimeField};
+use midnight_curves::bls12_381::Fq;
+use midnight_proofs::circuit::{Layouter, SimpleFloorPlanner, Value};
+use midnight_proofs::plonk::{
+    Advice, Any, Assignment, Challenge, Circuit, Column, ConstraintSystem, Error, Fixed,
+    FloorPlanner, Instance, Selector,
+};
+use midnight_proofs::utils::rational::Rational;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A row of the circuit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LayoutRow {
+    /// Index of the row.
+    pub row: usize,
+    /// The regions with cells in this row.
+    pub regions: Vec<String>,
+    /// The block compressed in this row, for the rows of the chip. The key, if any, is block 0.
+    pub block: Option<usize>,
+    /// The round of the compression, for the rows of a mix.
+    pub round: Option<usize>,
+    /// The index of the mix in its round, for the rows of a mix.
+    pub mix: Option<usize>,
+    /// The operation of the chip this row belongs to, or empty outside the region of the chip.
+    pub operation: String,
+    /// Indices of the advice columns assigned in this row.
+    pub advice_columns: Vec<usize>,
+    /// Names of the selectors enabled in this row.
+    pub selectors: Vec<&'static str>,
+}
+
+/// A region of the circuit, lookup tables included.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutRegion {
+    /// Name of the region.
+    pub name: String,
+    /// The first row with an assigned advice or fixed cell of the region, which is the row the
+    /// [midnight_proofs::dev::MockProver] measures its offsets from.
+    pub start: Option<usize>,
+}
+
+/// An advice cell of the circuit, with the region and annotation it was assigned with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AdviceCell {
+    /// Name of the region the cell was assigned in, or empty outside any region.
+    pub region: String,
+    /// The annotation of the assignment.
+    pub annotation: String,
+    /// Index of the advice column.
+    pub column: usize,
+    /// Index of the row.
+    pub row: usize,
+}
+
+/// A cell of any kind of column, as a (column, row) pair.
+pub type AnyCell = (Column<Any>, usize);
+
+/// The rows of the circuit with some assigned advice cell or enabled selector.
+#[derive(Clone, Debug)]
+pub struct CircuitLayout {
+    /// Number of advice columns of the circuit.
+    pub advice_columns: usize,
+    /// The regions, in the order they are assigned, which is also the order of their indices.
+    pub regions: Vec<LayoutRegion>,
+    /// The rows, in order.
+    pub rows: Vec<LayoutRow>,
+}
+
+impl CircuitLayout {
+    /// The row with the given index, if it has some assigned advice cell or enabled selector.
+    pub fn row(&self, index: usize) -> Option<&LayoutRow> {
+        self.rows.binary_search_by_key(&index, |row| row.row).ok().map(|i| &self.rows[i])
+    }
+
+    /// The layout as a text table. The advice column shows a '#' for every assigned advice cell
+    /// and a '.' for every unassigned one, in the order of the columns.
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "{:>7} | {:<13} | {:>5} | {:>5} | {:>3} | {:<32} | {:<advice$} | selectors\n",
+            "row",
+            "region",
+            "block",
+            "round",
+            "mix",
+            "operation",
+            "advice",
+            advice = self.advice_columns
+        );
+        for row in &self.rows {
+            let optional = |index: Option<usize>| index.map(|i| i.to_string()).unwrap_or_default();
+            let advice: String = (0..self.advice_columns)
+                .map(|column| if row.advice_columns.contains(&column) { '#' } else { '.' })
+                .collect();
+            table.push_str(&format!(
+                "{:>7} | {:<13} | {:>5} | {:>5} | {:>3} | {:<32} | {advice} | {}\n",
+                row.row,
+                row.regions.join(", "),
+                optional(row.block),
+                optional(row.round),
+                optional(row.mix),
+                row.operation,
+                row.selectors.join(" "),
+            ));
+        }
+        table
+    }
+}
+
+/// Lays out the circuit of [Blake2bCircuit::for_sizes] for the given sizes.
+pub fn circuit_layout(
+    input_len: usize,
+    key_len: usize,
+    output_len: usize,
+) -> Result<CircuitLayout, Blake2bError> {
+    let circuit = Blake2bCircuit::<Fq>::for_sizes(input_len, key_len, output_len)?;
+    circuit_layout_of(&circuit)
+}
+
+/// Lays out 'circuit', with its instance mode, digest layout and public input bytes. The rows of
+/// the region of the chip are labelled by the chip itself while it assigns them. The layout
+/// doesn't depend on the values of the circuit, which are ignored.
+pub fn circuit_layout_of<F: PrimeField>(
+    circuit: &Blake2bCircuit<F>,
+) -> Result<CircuitLayout, Blake2bError> {
+    let Recording {
+        meta,
+        config,
+        row_labels,
+        mut recorder,
+    } = record(circuit)?;
+    let selector_names: BTreeMap<usize, &'static str> = config
+        .0
+        .named_selectors()
+        .iter()
+        .map(|(name, selector)| (selector.index(), *name))
+        .collect();
+
+    // The start of the last region the chip labelled, which is the one its labels refer to
+    let labelled_region = row_labels.region().and_then(|name| {
+        let region = recorder.regions.iter().rev().find(|region| region.name == name)?;
+        Some((name, region.start?))
+    });
+
+    let rows = std::mem::take(&mut recorder.rows)
+        .into_iter()
+        .map(|(index, recorded)| {
+            let mut row = LayoutRow {
+                row: index,
+                regions: recorded.regions.into_iter().collect(),
+                advice_columns: recorded.advice_columns.into_iter().collect(),
+                selectors: recorded.selectors.iter().map(|index| selector_names[index]).collect(),
+                ..LayoutRow::default()
+            };
+            let label = labelled_region
+                .as_ref()
+                .filter(|(name, start)| index >= *start && row.regions.contains(name))
+                .and_then(|(_, start)| row_labels.label_at(index - start));
+            if let Some(label) = label {
+                row.block = label.block;
+                row.round = label.round;
+                row.mix = label.mix;
+                row.operation = label.operation;
+            }
+            row
+        })
+        .collect();
+    Ok(CircuitLayout {
+        advice_columns: meta.num_advice_columns(),
+        regions: recorder.regions,
+        rows,
+    })
+}
+
+/// The advice cells, enabled selectors and copy constraints of a synthesis of a [Blake2bCircuit],
+/// along with the constraint system it was configured in.
+pub(crate) struct RecordedCells<F: PrimeField> {
+    pub(crate) meta: ConstraintSystem<F>,
+    /// Every advice cell, in assignment order.
+    pub(crate) advice_cells: Vec<AdviceCell>,
+    /// The (selector index, row) of every enabled selector.
+    pub(crate) enabled_selectors: Vec<(usize, usize)>,
+    /// Every copy constraint, between two cells of any kind of column.
+    pub(crate) copies: Vec<(AnyCell, AnyCell)>,
+}
+
+/// Records the cells of 'circuit', which don't depend on its values.
+pub(crate) fn recorded_cells_of<F: PrimeField>(
+    circuit: &Blake2bCircuit<F>,
+) -> Result<RecordedCells<F>, Blake2bError> {
+    let Recording { meta, recorder, .. } = record(circuit)?;
+    let enabled_selectors = recorder
+        .rows
+        .iter()
+        .flat_map(|(row, recorded)| recorded.selectors.iter().map(|selector| (*selector, *row)))
+        .collect();
+    Ok(RecordedCells {
+        meta,
+        advice_cells: recorder.advice_cells,
+        enabled_selectors,
+        copies: recorder.copies,
+    })
+}
+
+/// A synthesis of a [Blake2bCircuit] by a [LayoutRecorder], with the rows of its chip labelled.
+struct Recording<F: PrimeField> {
+    meta: ConstraintSystem<F>,
+    config: <Blake2bCircuit<F> as Circuit<F>>::Config,
+    row_labels: RowLabels,
+    recorder: LayoutRecorder,
+}
+
+/// Configures 'circuit' and synthesizes it without its values through a [LayoutRecorder].
+fn record<F: PrimeField>(circuit: &Blake2bCircuit<F>) -> Result<Recording<F>, Blake2bError> {
+    let circuit = LabelledCircuit {
+        circuit: &circuit.without_witnesses(),
+        row_labels: RowLabels::default(),
+    };
+    let mut meta = ConstraintSystem::<F>::default();
+    let config = LabelledCircuit::configure_with_params(&mut meta, circuit.params());
+    let mut recorder = LayoutRecorder::default();
+    SimpleFloorPlanner::synthesize(
+        &mut recorder,
+        &circuit,
+        config.clone(),
+        meta.constants().clone(),
+    )?;
+    Ok(Recording {
+        meta,
+        config,
+        row_labels: circuit.row_labels,
+        recorder,
+    })
+}
+
+/// A [Blake2bCircuit] synthesized with a chip that records the labels of its rows.
+struct LabelledCircuit<'a, F: PrimeField> {
+    circuit: &'a Blake2bCircuit<F>,
+    row_labels: RowLabels,
+}
+
+impl<F: PrimeField> Circuit<F> for LabelledCircuit<'_, F> {
+    type Config = <Blake2bCircuit<F> as Circuit<F>>::Config;
+    type Params = Blake2bInstanceMode;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit,
+            row_labels: self.row_labels.clone(),
+        }
+    }
+
+    fn params(&self) -> Self::Params {
+        self.circuit.params()
+    }
+
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<F>,
+        instance_mode: Self::Params,
+    ) -> Self::Config {
+        Blake2bCircuit::configure_with_params(meta, instance_mode)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        Blake2bCircuit::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<F>) -> Result<(), Error> {
+        let chip = Blake2bChip::new(&config.0).with_row_labels(self.row_labels.clone());
+        self.circuit.synthesize_with_chip(config, layouter, chip)
+    }
+}
+
+/// What is assigned in a row of the circuit.
+#[derive(Debug, Default)]
+struct RecordedRow {
+    regions: BTreeSet<String>,
+    advice_columns: BTreeSet<usize>,
+    selectors: BTreeSet<usize>,
+}
+
+/// An [Assignment] that only records the regions, advice cells and selectors of every row, and
+/// the copy constraints. The values are never computed.
+#[derive(Debug, Default)]
+struct LayoutRecorder {
+    /// Index of the region being assigned, if any.
+    region: Option<usize>,
+    regions: Vec<LayoutRegion>,
+    rows: BTreeMap<usize, RecordedRow>,
+    advice_cells: Vec<AdviceCell>,
+    copies: Vec<(AnyCell, AnyCell)>,
+}
+
+impl LayoutRecorder {
+    fn row(&mut self, row: usize) -> &mut RecordedRow {
+        let recorded = self.rows.entry(row).or_default();
+        if let Some(region) = self.region {
+            recorded.regions.insert(self.regions[region].name.clone());
+        }
+        recorded
+    }
+
+    /// Extends the current region, if any, to a row with an assigned cell.
+    fn extend_region(&mut self, row: usize) {
+        if let Some(region) = self.region {
+            let start = &mut self.regions[region].start;
+            *start = Some(start.map_or(row, |start| start.min(row)));
+        }
+    }
+}
+
+impl<F: Field> Assignment<F> for LayoutRecorder {
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.region = Some(self.regions.len());
+        self.regions.push(LayoutRegion {
+            name: name_fn().into(),
+            start: None,
+        });
+    }
+
+    fn annotate_column<A, AR>(&mut self, _annotation: A, _column: Column<Any>)
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+    }
+
+    fn exit_region(&mut self) {
+        self.region = None;
+    }
+
+    fn enable_selector<A, AR>(
+        &mut self,
+        _annotation: A,
+        selector: &Selector,
+        row: usize,
+    ) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.row(row).selectors.insert(selector.index());
+        Ok(())
+    }
+
+    fn query_instance(&self, _column: Column<Instance>, _row: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        row: usize,
+        _to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Rational<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.extend_region(row);
+        self.row(row).advice_columns.insert(column.index());
+        let region = self.region.map(|region| self.regions[region].name.clone());
+        self.advice_cells.push(AdviceCell {
+            region: region.unwrap_or_default(),
+            annotation: annotation().into(),
+            column: column.index(),
+            row,
+        });
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _annotation: A,
+        _column: Column<Fixed>,
+        row: usize,
+        _to: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Rational<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.extend_region(row);
+        Ok(())
+    }
+
+    fn copy(
+        &mut self,
+        left_column: Column<Any>,
+        left_row: usize,
+        right_column: Column<Any>,
+        right_row: usize,
+    ) -> Result<(), Error> {
+        self.copies.push(((left_column, left_row), (right_column, right_row)));
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _column: Column<Fixed>,
+        _row: usize,
+        _to: Value<Rational<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get_challenge(&self, _challenge: Challenge) -> Value<F> {
+        Value::unknown()
+    }
+
+    fn push_namespace<NR, N>(&mut self, _name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _gadget_name: Option<String>) {}
+}
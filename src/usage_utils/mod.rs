@@ -1,6 +1,23 @@
 //! Module that implements an example Blake2bCircuit that uses our Blake2bChip
 //! It also has a CircuitRunner that helps to preprocess inputs, synthesize, prove and verify
-//! the circuit. Used for testing and benchmarking purposes.
+//! the circuit. Used for testing and benchmarking purposes. The cost report describes the
+//! resources the circuit needs for a given input size, and the verifying key fingerprint tells
+//! whether a change to the chip changes the verifying keys of the circuit. The layout shows
+//! what every row of the circuit holds, which the diagnostics use to explain the failures of the
+//! Mock Prover in terms of the hash, and the constraint coverage tells which constraints touch
+//! every advice cell the chip assigns. These three are dev tools, only public with the dev-graph
+//! feature.
 
 pub mod blake2b_circuit;
 pub mod circuit_runner;
+#[cfg(any(test, feature = "dev-graph"))]
+pub mod constraint_coverage;
+pub mod cost_report;
+#[cfg(any(test, feature = "dev-graph"))]
+pub mod diagnostics;
+// The cost report counts the rows of the lookups with the layout, so it is always compiled
+#[cfg(feature = "dev-graph")]
+pub mod layout;
+#[cfg(not(feature = "dev-graph"))]
+pub(crate) mod layout;
+pub mod vk_fingerprint;
@@ -0,0 +1,113 @@
+//! Fingerprints of the verifying keys of the example [Blake2bCircuit]. Any change to the gates,
+//! lookups or layout of the chip changes the verifying keys of the deployed circuits, so the
+//! fingerprints of some representative sizes are pinned in 'vk_fingerprints.json' and checked by
+//! the tests.
+
+use crate::error::Blake2bError;
+use crate::usage_utils::blake2b_circuit::Blake2bCircuit;
+use crate::usage_utils::circuit_runner::Bls12CircuitRunner;
+use midnight_curves::bls12_381::{Bls12, Fq};
+use ff::PrimeField;
+use midnight_proofs::plonk::{Any, ConstraintSystem, Expression};
+use midnight_proofs::poly::kzg::params::ParamsKZG;
+use midnight_proofs::poly::Rotation;
+use midnight_proofs::utils::SerdeFormat;
+use rand_chacha::rand_core::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// Seed of the parameters the fingerprinted verifying keys are generated with.
+const FINGERPRINT_PARAMS_SEED: u64 = 0;
+
+/// The BLAKE2b-256 hash of the verifying key of the circuit of [Blake2bCircuit::for_sizes] for the
+/// given sizes. The key is generated with parameters from a fixed seed, so the fingerprint only
+/// depends on the circuit and not on the parameters of a deployment.
+///
+/// The serialized key only holds the commitments to the fixed and permutation columns, so the
+/// gates and lookups of its constraint system are hashed along with it, in the encoding of
+/// [constraint_system_bytes].
+pub fn vk_fingerprint(
+    input_len: usize,
+    key_len: usize,
+    output_len: usize,
+) -> Result<[u8; 32], Blake2bError> {
+    let circuit = Blake2bCircuit::<Fq>::for_sizes(input_len, key_len, output_len)?;
+    let rng = ChaCha20Rng::seed_from_u64(FINGERPRINT_PARAMS_SEED);
+    let params = ParamsKZG::<Bls12>::unsafe_setup(circuit.k(), rng);
+    let vk = Bls12CircuitRunner::create_vk(&circuit, &params)?;
+
+    let digest = blake2b_simd::Params::new()
+        .hash_length(32)
+        .to_state()
+        .update(&vk.to_bytes(SerdeFormat::RawBytes))
+        .update(&constraint_system_bytes(vk.cs()))
+        .finalize();
+    Ok(digest.as_bytes().try_into().expect("The digest should have 32 bytes"))
+}
+
+/// A canonical encoding of the column counts, gates, lookups and permutation columns of 'cs'. Unlike
+/// its `Debug` output, it doesn't change with the formatting of midnight-proofs types.
+fn constraint_system_bytes<F: PrimeField>(cs: &ConstraintSystem<F>) -> Vec<u8> {
+    let mut bytes = vec![];
+    for count in [
+        cs.num_fixed_columns(),
+        cs.num_advice_columns(),
+        cs.num_instance_columns(),
+        cs.num_selectors(),
+        cs.num_challenges(),
+    ] {
+        push_usize(&mut bytes, count);
+    }
+    let polynomials = cs.gates().iter().flat_map(|gate| gate.polynomials());
+    push_expressions(&mut bytes, polynomials.collect::<Vec<_>>());
+    push_usize(&mut bytes, cs.lookups().len());
+    for lookup in cs.lookups() {
+        push_expressions(&mut bytes, lookup.input_expressions().iter().collect());
+        push_expressions(&mut bytes, lookup.table_expressions().iter().collect());
+    }
+    let columns = cs.permutation().get_columns();
+    push_usize(&mut bytes, columns.len());
+    for column in columns {
+        let column_type = match column.column_type() {
+            Any::Fixed => 0,
+            Any::Advice(_) => 1,
+            Any::Instance => 2,
+        };
+        bytes.push(column_type);
+        push_usize(&mut bytes, column.index());
+    }
+    bytes
+}
+
+fn push_usize(bytes: &mut Vec<u8>, value: usize) {
+    bytes.extend((value as u64).to_le_bytes());
+}
+
+fn push_expressions<F: PrimeField>(bytes: &mut Vec<u8>, expressions: Vec<&Expression<F>>) {
+    push_usize(bytes, expressions.len());
+    for expression in expressions {
+        bytes.extend(expression_bytes(expression));
+    }
+}
+
+/// The prefix encoding of 'expression': a tag per node followed by its fields or operands.
+fn expression_bytes<F: PrimeField>(expression: &Expression<F>) -> Vec<u8> {
+    let query = |tag: u8, column: usize, rotation: Rotation| {
+        let mut bytes = vec![tag];
+        push_usize(&mut bytes, column);
+        bytes.extend(rotation.0.to_le_bytes());
+        bytes
+    };
+    let node = |tag: u8, operands: &[Vec<u8>]| [&[tag][..], &operands.concat()].concat();
+    expression.evaluate(
+        &|constant| [&[0][..], constant.to_repr().as_ref()].concat(),
+        &|selector| node(1, &[(selector.index() as u64).to_le_bytes().to_vec()]),
+        &|fixed| query(2, fixed.column_index(), fixed.rotation()),
+        &|advice| query(3, advice.column_index(), advice.rotation()),
+        &|instance| query(4, instance.column_index(), instance.rotation()),
+        &|challenge| node(5, &[(challenge.index() as u64).to_le_bytes().to_vec()]),
+        &|a| node(6, &[a]),
+        &|a, b| node(7, &[a, b]),
+        &|a, b| node(8, &[a, b]),
+        &|a, scalar| node(9, &[a, scalar.to_repr().as_ref().to_vec()]),
+    )
+}
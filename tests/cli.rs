@@ -0,0 +1,152 @@
+//! Runs the blake2b-halo2 command-line tool on files in a temporary directory, as a user would.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// A directory for the files of a test, removed when it is dropped.
+struct TestDir(PathBuf);
+
+impl TestDir {
+    fn new(name: &str) -> Self {
+        let path =
+            std::env::temp_dir().join(format!("blake2b_halo2_cli_{name}_{}", std::process::id()));
+        fs::create_dir_all(&path).unwrap();
+        Self(path)
+    }
+
+    /// The path, as a string argument, of the file 'name' in the directory.
+    fn file(&self, name: &str) -> String {
+        self.0.join(name).to_str().unwrap().to_string()
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_blake2b-halo2")).args(args).output().unwrap()
+}
+
+fn run_successfully(args: &[&str]) -> Output {
+    let output = run(args);
+    assert!(
+        output.status.success(),
+        "{args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output
+}
+
+/// Writes the message and key files, and the params and keys for their sizes and a digest of 32
+/// bytes.
+fn setup_and_keygen(dir: &TestDir, message: &[u8], key: &[u8]) {
+    fs::write(dir.file("message.bin"), message).unwrap();
+    fs::write(dir.file("key.bin"), key).unwrap();
+    let (input_size, key_size) = (message.len().to_string(), key.len().to_string());
+    let params = dir.file("params.bin");
+    run_successfully(&[
+        "setup",
+        "--params",
+        &params,
+        "--input-size",
+        &input_size,
+        "--key-size",
+        &key_size,
+    ]);
+    run_successfully(&[
+        "keygen",
+        "--params",
+        &params,
+        "--input-size",
+        &input_size,
+        "--key-size",
+        &key_size,
+        "--output-size",
+        "32",
+        "--vk",
+        &dir.file("vk.bin"),
+        "--pk",
+        &dir.file("pk.bin"),
+    ]);
+}
+
+fn verify(dir: &TestDir) -> Output {
+    run(&[
+        "verify",
+        "--params",
+        &dir.file("params.bin"),
+        "--vk",
+        &dir.file("vk.bin"),
+        "--proof",
+        &dir.file("proof.bin"),
+        "--digest",
+        &dir.file("digest.hex"),
+    ])
+}
+
+#[test]
+fn test_setup_keygen_prove_and_verify() {
+    let dir = TestDir::new("round_trip");
+    let (message, key) = (b"hello world", b"secret");
+    setup_and_keygen(&dir, message, key);
+
+    run_successfully(&[
+        "prove",
+        "--params",
+        &dir.file("params.bin"),
+        "--pk",
+        &dir.file("pk.bin"),
+        "--message",
+        &dir.file("message.bin"),
+        "--key",
+        &dir.file("key.bin"),
+        "--output-size",
+        "32",
+        "--proof",
+        &dir.file("proof.bin"),
+        "--digest",
+        &dir.file("digest.hex"),
+    ]);
+    let expected_digest = blake2b_simd::Params::new().hash_length(32).key(key).hash(message);
+    let digest = fs::read_to_string(dir.file("digest.hex")).unwrap();
+    assert_eq!(digest.trim(), expected_digest.to_hex().as_str());
+
+    let output = verify(&dir);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "The proof is valid\n");
+
+    // The proof doesn't verify against the digest of another message
+    let other_digest = blake2b_simd::Params::new().hash_length(32).key(key).hash(b"hello worle");
+    fs::write(dir.file("digest.hex"), other_digest.to_hex().as_str()).unwrap();
+    let output = verify(&dir);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+}
+
+#[test]
+fn test_verify_fails_with_an_invalid_proof() {
+    let dir = TestDir::new("invalid_proof");
+    setup_and_keygen(&dir, b"abc", b"");
+    fs::write(dir.file("proof.bin"), [0u8; 64]).unwrap();
+    let digest = blake2b_simd::Params::new().hash_length(32).hash(b"abc");
+    fs::write(dir.file("digest.hex"), digest.to_hex().as_str()).unwrap();
+
+    let output = verify(&dir);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: "));
+}
+
+#[test]
+fn test_commands_fail_with_missing_files() {
+    let dir = TestDir::new("missing_files");
+    let output = verify(&dir);
+
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("Error: I/O error"));
+}